@@ -1,6 +1,6 @@
 use course_helpers::{
-    hill_climber::{HillClimber, HillClimberError},
-    inspector::update_best,
+    hill_climber::{AlwaysReplace, HillClimber, HillClimberError},
+    inspector::{on_samples, update_best},
 };
 use ec_core::{
     distributions::collection::ConvertToCollectionGenerator,
@@ -33,11 +33,13 @@ fn main() -> Result<(), HillClimberError<GenomeSizeConversionError>> {
     let mut hill_climber = HillClimber::builder()
         .num_to_search(num_to_create)
         .num_children_per_step(10)
-        .always_replace(true)
+        .acceptance(AlwaysReplace)
         .genome_maker(genome_maker)
         .mutator(WithOneOverLength)
         .scorer(scorer)
-        .inspector(|solution_chunk| update_best(&mut best, solution_chunk))
+        .inspector(on_samples(|solution_chunk| {
+            update_best(&mut best, solution_chunk);
+        }))
         .build();
 
     hill_climber.search()?;
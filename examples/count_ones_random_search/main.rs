@@ -1,4 +1,7 @@
-use course_helpers::{inspector::update_best, random_search::RandomSearch};
+use course_helpers::{
+    inspector::{on_samples, update_best},
+    random_search::RandomSearch,
+};
 use ec_core::{
     distributions::collection::ConvertToCollectionGenerator,
     individual::scorer::FnScorer,
@@ -28,9 +31,9 @@ fn main() {
         .num_to_search(num_to_create)
         .genome_maker(genome_maker)
         .scorer(scorer)
-        .inspector(|solution_chunk| {
+        .inspector(on_samples(|solution_chunk| {
             update_best(&mut best, solution_chunk);
-        })
+        }))
         .parallel_search(true)
         .build();
 
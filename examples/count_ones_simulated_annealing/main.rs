@@ -0,0 +1,53 @@
+use course_helpers::{
+    inspector::{on_samples, update_best},
+    simulated_annealing::{CoolingSchedule, SimulatedAnnealing, SimulatedAnnealingError},
+};
+use ec_core::{
+    distributions::collection::ConvertToCollectionGenerator,
+    individual::scorer::FnScorer,
+    test_results::{Score, TestResults},
+};
+use ec_linear::{
+    genome::bitstring::Bitstring,
+    mutator::with_one_over_length::{GenomeSizeConversionError, WithOneOverLength},
+};
+use rand::distr::StandardUniform;
+
+#[must_use]
+pub fn count_ones(bits: &[bool]) -> TestResults<Score<u64>> {
+    bits.iter().copied().map(u64::from).collect()
+}
+
+fn main() -> Result<(), SimulatedAnnealingError<GenomeSizeConversionError>> {
+    let num_to_create = 100_000;
+
+    let num_bits = 128;
+
+    let scorer = FnScorer(|bitstring: &Bitstring| count_ones(&bitstring.bits));
+
+    // Create a `Distribution` that generates `Bitstring`s when sampled
+    let genome_maker = StandardUniform.into_collection_generator(num_bits);
+
+    let mut best = None;
+
+    // Early on, a child with one fewer one than the current solution is
+    // accepted about 1 time in 7 (exp(-1/0.5)), but by the end of the search
+    // the temperature is so low that only improvements (or ties) are accepted.
+    let mut simulated_annealing = SimulatedAnnealing::builder()
+        .num_to_search(num_to_create)
+        .cooling_schedule(CoolingSchedule::Geometric {
+            initial: 0.5,
+            alpha: 0.9999,
+        })
+        .genome_maker(genome_maker)
+        .mutator(WithOneOverLength)
+        .scorer(scorer)
+        .inspector(on_samples(|solution_chunk| {
+            update_best(&mut best, solution_chunk);
+        }))
+        .build();
+
+    simulated_annealing.search()?;
+
+    Ok(())
+}
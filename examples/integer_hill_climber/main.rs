@@ -1,7 +1,10 @@
 use rand::distr::{uniform, Distribution, StandardUniform, Uniform};
 use std::convert::Infallible;
 
-use course_helpers::{hill_climber::HillClimber, inspector::update_best};
+use course_helpers::{
+    hill_climber::HillClimber,
+    inspector::{print_best, BestTracker, Direction},
+};
 use ec_core::{individual::scorer::FnScorer, operator::mutator::Mutator, test_results::Error};
 
 struct IntegerMutator {
@@ -31,19 +34,27 @@ fn main() -> anyhow::Result<()> {
     // Create a `Distribution` that generates `i64`s when sampled
     let genome_maker = StandardUniform;
 
-    let mut best = None;
+    // `Error` orders lower errors as "higher", so maximizing the score finds
+    // the genome with the lowest error.
+    let mut best = BestTracker::builder()
+        .direction(Direction::Maximize)
+        .printer(print_best)
+        .build();
 
     let mut hill_climber = HillClimber::builder()
         .num_to_search(num_to_create)
         .num_children_per_step(10)
-        .always_replace(false)
         .genome_maker(genome_maker)
         .mutator(IntegerMutator::new(100_000)?)
         .scorer(scorer)
-        .inspector(|solution_chunk| update_best(&mut best, solution_chunk))
+        .inspector(&mut best)
         .build();
 
     hill_climber.search()?;
+    println!(
+        "Improved the best solution {} times",
+        best.trajectory().len()
+    );
 
     Ok(())
 }
@@ -1,4 +1,10 @@
-use course_helpers::{inspector::update_best, random_search::RandomSearch};
+use std::thread;
+
+use course_helpers::{
+    inspector::{print_best, BestTracker, Direction, Forwarder, WhenFull},
+    random_search::RandomSearch,
+    termination::Termination,
+};
 use ec_core::{individual::scorer::FnScorer, test_results::Error};
 use rand::distr::StandardUniform;
 
@@ -10,24 +16,44 @@ fn main() {
     // Create a `Distribution` that generates `i64`s when sampled
     let genome_maker = StandardUniform;
 
-    let mut best = None;
+    // Forward the solution chunks to a separate thread that keeps track of (and
+    // prints) the best solution, so the search never waits on the printing.
+    let (mut forwarder, receiver) = Forwarder::<Vec<_>>::bounded(100, WhenFull::Block);
+    let consumer = thread::spawn(move || {
+        // `Error` orders lower errors as "higher", so maximizing the score
+        // finds the genome with the lowest error.
+        let mut best = BestTracker::builder()
+            .direction(Direction::Maximize)
+            .printer(print_best)
+            .build();
+        for solution_chunk in receiver {
+            best.record_all(&solution_chunk);
+        }
+    });
 
-    // let mut random_search = RandomSearch::new(num_to_create, genome_maker, scorer, sender.clone());
     let mut random_search = RandomSearch::builder()
         .num_to_search(num_to_create)
         .genome_maker(genome_maker)
         .scorer(scorer)
-        .inspector(|solution_chunk| {
-            update_best(&mut best, solution_chunk);
-        })
+        .inspector(&mut forwarder)
         .parallel_search(true)
+        // There's no point searching any further once we've found the target.
+        .termination(Termination::TargetScore(Error(0)))
         .build();
 
-    random_search.search();
+    let report = random_search.search();
+    // Dropping the forwarder closes the channel, so the consumer thread finishes
+    // once it has handled everything we sent it.
+    drop(forwarder);
+    consumer.join().expect("The consumer thread panicked");
 
-    let (best_sample_number, best_genome, best_score) = best.unwrap();
+    let best = report.best.unwrap();
     println!(
         "Best solution found: sample_number: {}, genome: {:?}, score: {}",
-        best_sample_number, best_genome, best_score
+        best.sample_number, best.genome, best.score
+    );
+    println!(
+        "Searched {} samples in {:?} (stopped because of {:?})",
+        report.evaluations, report.elapsed, report.termination_reason
     );
 }
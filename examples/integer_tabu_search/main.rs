@@ -0,0 +1,63 @@
+use rand::distr::{uniform, Distribution, StandardUniform, Uniform};
+use std::convert::Infallible;
+
+use course_helpers::{
+    inspector::{print_best, BestTracker, Direction},
+    tabu_search::TabuSearch,
+};
+use ec_core::{individual::scorer::FnScorer, operator::mutator::Mutator, test_results::Error};
+
+struct IntegerMutator {
+    distribution: Uniform<i32>,
+}
+
+impl IntegerMutator {
+    pub fn new(max_step: i32) -> Result<Self, uniform::Error> {
+        let distribution = Uniform::new(-max_step, max_step)?;
+        Ok(Self { distribution })
+    }
+}
+
+impl Mutator<i32> for IntegerMutator {
+    type Error = Infallible;
+
+    fn mutate<R: rand::Rng + ?Sized>(&self, genome: i32, rng: &mut R) -> Result<i32, Self::Error> {
+        Ok(genome.saturating_add(self.distribution.sample(rng)))
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let num_to_create = 1_000_000;
+    let target: i32 = 589;
+    let scorer = FnScorer(|value: &i32| Error(value.abs_diff(target)));
+
+    // Create a `Distribution` that generates `i32`s when sampled
+    let genome_maker = StandardUniform;
+
+    // `Error` orders lower errors as "higher", so maximizing the score finds
+    // the genome with the lowest error.
+    let mut best = BestTracker::builder()
+        .direction(Direction::Maximize)
+        .printer(print_best)
+        .build();
+
+    // Unlike a hill climber, tabu search always moves to the best of the
+    // neighbors it hasn't recently visited, even if that's a step backwards.
+    let mut tabu_search = TabuSearch::builder()
+        .num_to_search(num_to_create)
+        .num_neighbors(10)
+        .tabu_tenure(50)
+        .genome_maker(genome_maker)
+        .mutator(IntegerMutator::new(100_000)?)
+        .scorer(scorer)
+        .inspector(&mut best)
+        .build();
+
+    tabu_search.search()?;
+    println!(
+        "Improved the best solution {} times",
+        best.trajectory().len()
+    );
+
+    Ok(())
+}
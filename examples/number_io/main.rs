@@ -7,23 +7,20 @@
 pub mod args;
 
 use clap::Parser;
-use course_helpers::simplifier::{drop_one::DropOne, Simplifier};
+use course_helpers::{
+    ec_run::{Run, RunReport, Variation},
+    inspector::on_generation,
+    simplifier::{drop_one::DropOne, Simplifier},
+    termination::{Termination, TerminationReason},
+};
 use ec_core::{
-    distributions::collection::ConvertToCollectionGenerator,
-    generation::Generation,
-    individual::{ec::WithScorer, scorer::FnScorer},
-    operator::{
-        genome_extractor::GenomeExtractor,
-        genome_scorer::GenomeScorer,
-        mutator::Mutate,
-        selector::{best::Best, tournament::Tournament, Select, Selector},
-        Composable,
-    },
+    individual::scorer::FnScorer,
+    operator::selector::{best::Best, tournament::Tournament, Selector},
     test_results::{self, TestResults},
     uniform_distribution_of,
 };
 use ec_linear::mutator::umad::Umad;
-use miette::ensure;
+use miette::miette;
 use ordered_float::OrderedFloat;
 use push::{
     evaluation::{Case, Cases, WithTargetFn},
@@ -34,7 +31,7 @@ use push::{
     },
     push_vm::{program::PushProgram, push_state::PushState, State},
 };
-use rand::{prelude::Distribution, rng, Rng};
+use rand::{rng, Rng};
 use strsim::damerau_levenshtein;
 
 use crate::args::{CliArgs, RunModel};
@@ -169,54 +166,45 @@ fn main() -> miette::Result<()> {
     ]
     .into_gene_generator();
 
-    let population = gene_generator
-        .to_collection_generator(max_initial_instructions)
-        .with_scorer(scorer)
-        .into_collection_generator(population_size)
-        .sample(&mut rng);
-
-    ensure!(
-        !population.is_empty(),
-        "An initial population is always required"
-    );
-
-    let mut best = Best.select(&population, &mut rng)?.clone();
-    println!("Best initial individual is {best}");
-
+    let genome_maker = gene_generator.to_collection_generator(max_initial_instructions);
     let umad = Umad::new(0.1, 0.1, &gene_generator);
 
-    let make_new_individual = Select::new(selector)
-        .then(GenomeExtractor)
-        .then(Mutate::new(umad))
-        .wrap::<GenomeScorer<_, _>>(scorer);
-
-    let mut generation = Generation::new(make_new_individual, population);
-
-    // TODO: It might be useful to insert some kind of logging system so we can
-    // make this less imperative in nature.
-
-    for generation_number in 0..max_generations {
-        match run_model {
-            RunModel::Serial => generation.serial_next()?,
-            RunModel::Parallel => generation.par_next()?,
-        }
-
-        best = Best.select(generation.population(), &mut rng)?.clone();
-        // TODO: Change 2 to be the smallest number of digits needed for
-        // max_generations-1.
-        println!("Generation {generation_number:2} best is {best}");
+    // Stop as soon as some program gets every training case exactly right.
+    let target: TestResults<test_results::Error<i128>> =
+        std::iter::repeat_n(0, training_cases.len()).collect();
+
+    let RunReport { search, .. } = Run::builder()
+        .genome_maker(genome_maker)
+        .population_size(population_size)
+        .max_generations(max_generations)
+        .termination(Termination::TargetScore(target))
+        .parallel_evaluation(matches!(run_model, RunModel::Parallel))
+        .scorer(scorer)
+        .selector(selector)
+        .variation(Variation::mutation(umad))
+        .inspector(on_generation(|generation_number, population| {
+            if let Ok(best) = Best.select(population, &mut rng) {
+                // TODO: Change 2 to be the smallest number of digits needed for
+                // max_generations-1.
+                println!("Generation {generation_number:2} best is {best}");
+            }
+        }))
+        .build()
+        .execute()
+        .map_err(|error| miette!("{error:#}"))?;
 
-        if best.test_results.total_result.0 == 0 {
-            println!("SUCCESS");
-            break;
-        }
+    if search.termination_reason == TerminationReason::TargetScore {
+        println!("SUCCESS");
     }
+    let best = search
+        .best
+        .ok_or_else(|| miette!("An initial population is always required"))?;
 
     // TODO: This should also be removed (or the number of simplifications set to 0) when
     // doing timing comparisons since DEAP doesn't do anything like simplification.
 
     let drop_one_simplifier = DropOne::new(scorer, 10_000, 0);
-    let simplified_best = drop_one_simplifier.simplify_genome(best.genome.clone(), &mut rng);
+    let simplified_best = drop_one_simplifier.simplify_genome(best.genome, &mut rng);
     println!("Simplified best is {simplified_best}");
 
     Ok(())
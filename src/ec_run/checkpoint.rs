@@ -0,0 +1,362 @@
+use std::{
+    fmt::Write as _,
+    fs,
+    num::ParseIntError,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use ec_linear::genome::bitstring::Bitstring;
+use push::{
+    genome::plushy::{Plushy, PushGene},
+    instruction::PushInstruction,
+};
+
+const HEADER: &str = "course-helpers checkpoint v1";
+
+#[derive(Debug, thiserror::Error)]
+pub enum CheckpointError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("Malformed checkpoint at line {line}: {message}")]
+    Format { line: usize, message: String },
+
+    #[error("Couldn't decode genome: {0}")]
+    Genome(String),
+
+    #[error("Couldn't encode genome: {0}")]
+    Encode(String),
+}
+
+impl From<ParseIntError> for CheckpointError {
+    fn from(error: ParseIntError) -> Self {
+        Self::Genome(error.to_string())
+    }
+}
+
+/// Converts genomes to and from a single line of text so they can be saved
+/// in a [`Checkpoint`].
+pub trait GenomeCodec<G> {
+    /// Encode `genome` as text. The result must not contain any newlines.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if `genome` can't be encoded by this codec.
+    fn encode(&self, genome: &G) -> Result<String, CheckpointError>;
+
+    /// # Errors
+    ///
+    /// This returns an error if `text` isn't a valid encoding of a genome.
+    fn decode(&self, text: &str) -> Result<G, CheckpointError>;
+}
+
+/// Encodes a `Bitstring` as a string of `0`s and `1`s.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BitstringCodec;
+
+impl GenomeCodec<Bitstring> for BitstringCodec {
+    fn encode(&self, genome: &Bitstring) -> Result<String, CheckpointError> {
+        Ok(genome
+            .bits
+            .iter()
+            .map(|&bit| if bit { '1' } else { '0' })
+            .collect())
+    }
+
+    fn decode(&self, text: &str) -> Result<Bitstring, CheckpointError> {
+        text.chars()
+            .map(|c| match c {
+                '0' => Ok(false),
+                '1' => Ok(true),
+                _ => Err(CheckpointError::Genome(format!("Invalid bit {c:?}"))),
+            })
+            .collect()
+    }
+}
+
+/// Encodes a `Plushy` as the positions of its instructions in the instruction
+/// set used to generate the genes, with `)` for a `Close` gene.
+///
+/// The instruction set has to be the same (and in the same order) when the
+/// checkpoint is loaded as when it was saved.
+#[derive(Debug, Clone)]
+pub struct PlushyCodec {
+    instructions: Vec<PushInstruction>,
+}
+
+impl PlushyCodec {
+    pub const fn new(instructions: Vec<PushInstruction>) -> Self {
+        Self { instructions }
+    }
+}
+
+impl GenomeCodec<Plushy> for PlushyCodec {
+    fn encode(&self, genome: &Plushy) -> Result<String, CheckpointError> {
+        let tokens = genome
+            .get_genes()
+            .iter()
+            .map(|gene| match gene {
+                PushGene::Close => Ok(")".to_string()),
+                PushGene::Instruction(instruction) => self
+                    .instructions
+                    .iter()
+                    .position(|candidate| candidate == instruction)
+                    .map(|index| index.to_string())
+                    .ok_or_else(|| {
+                        CheckpointError::Encode(format!(
+                            "{instruction} isn't in the codec's instruction set"
+                        ))
+                    }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(tokens.join(" "))
+    }
+
+    fn decode(&self, text: &str) -> Result<Plushy, CheckpointError> {
+        let genes = text
+            .split_whitespace()
+            .map(|token| {
+                if token == ")" {
+                    return Ok(PushGene::Close);
+                }
+                let index: usize = token.parse()?;
+                self.instructions
+                    .get(index)
+                    .cloned()
+                    .map(PushGene::Instruction)
+                    .ok_or_else(|| {
+                        CheckpointError::Genome(format!("No instruction with index {index}"))
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Plushy::new(genes))
+    }
+}
+
+/// How often, and where, a [`Run`](super::Run) should save checkpoints.
+pub struct Checkpointing<G> {
+    pub(crate) path: PathBuf,
+    pub(crate) every_n_generations: usize,
+    pub(crate) codec: Box<dyn GenomeCodec<G> + Send + Sync>,
+}
+
+impl<G> Checkpointing<G> {
+    /// Save a checkpoint to `path` every `every_n_generations` generations,
+    /// overwriting the previous checkpoint. A run with an interval of zero
+    /// fails to start.
+    pub fn new(
+        path: impl Into<PathBuf>,
+        every_n_generations: usize,
+        codec: impl GenomeCodec<G> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            path: path.into(),
+            every_n_generations,
+            codec: Box::new(codec),
+        }
+    }
+}
+
+/// The state of a [`Run`](super::Run) at the end of a generation.
+///
+/// We don't save the scores, since we can't generally convert them to text;
+/// the population (and the best genome) are rescored when a run is resumed.
+/// We also don't need to save the RNG state because each generation's random
+/// number generators are derived from the seed and the generation number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkpoint<G> {
+    pub seed: u64,
+    pub generation: usize,
+    pub evaluations: usize,
+    /// The best genome seen so far, with its sample number and the generation
+    /// it was found in.
+    pub best: Option<(usize, usize, G)>,
+    pub population: Vec<G>,
+}
+
+impl<G> Checkpoint<G> {
+    /// Save this checkpoint to `path`.
+    ///
+    /// The checkpoint is written to a temporary file first and then renamed,
+    /// so a crash while saving doesn't destroy the previous checkpoint.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if encoding a genome fails or if writing the file
+    /// fails. In either case the previous checkpoint (if any) is left alone.
+    pub fn save(
+        &self,
+        path: impl AsRef<Path>,
+        codec: &(impl GenomeCodec<G> + ?Sized),
+    ) -> Result<(), CheckpointError> {
+        let mut contents = String::new();
+        // Writing to a `String` can't fail.
+        let _ = writeln!(contents, "{HEADER}");
+        let _ = writeln!(contents, "seed {}", self.seed);
+        let _ = writeln!(contents, "generation {}", self.generation);
+        let _ = writeln!(contents, "evaluations {}", self.evaluations);
+        match &self.best {
+            None => {
+                let _ = writeln!(contents, "best none");
+            }
+            Some((sample_number, step, genome)) => {
+                let _ = writeln!(
+                    contents,
+                    "best {sample_number} {step} {}",
+                    codec.encode(genome)?
+                );
+            }
+        }
+        let _ = writeln!(contents, "population {}", self.population.len());
+        for genome in &self.population {
+            let _ = writeln!(contents, "{}", codec.encode(genome)?);
+        }
+
+        let path = path.as_ref();
+        // We add `.tmp` to the whole file name (rather than replacing the
+        // extension), so `a.json` and `a.txt` don't share a temporary file.
+        let mut temp_path = path.as_os_str().to_owned();
+        temp_path.push(".tmp");
+        let temp_path = PathBuf::from(temp_path);
+        fs::write(&temp_path, contents)?;
+        fs::rename(temp_path, path)?;
+        Ok(())
+    }
+
+    /// Load a checkpoint previously written by [`Checkpoint::save`].
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if the file can't be read, or if it isn't a
+    /// valid checkpoint.
+    pub fn load(
+        path: impl AsRef<Path>,
+        codec: &(impl GenomeCodec<G> + ?Sized),
+    ) -> Result<Self, CheckpointError> {
+        let contents = fs::read_to_string(path)?;
+        let mut lines = contents.lines().enumerate();
+        let mut next_line = |expected: &str| {
+            lines
+                .next()
+                .map(|(index, text)| (index + 1, text))
+                .ok_or_else(|| format_error(0, format!("Missing {expected}")))
+        };
+
+        let (line, header) = next_line("header")?;
+        if header != HEADER {
+            return Err(format_error(line, "Not a course-helpers checkpoint"));
+        }
+        let seed = parse_field(next_line("seed")?, "seed")?;
+        let generation = parse_field(next_line("generation")?, "generation")?;
+        let evaluations = parse_field(next_line("evaluations")?, "evaluations")?;
+
+        let (line, best_line) = next_line("best")?;
+        let best = match best_line.strip_prefix("best ") {
+            Some("none") => None,
+            Some(rest) => {
+                let mut parts = rest.splitn(3, ' ');
+                let mut number = || {
+                    parts
+                        .next()
+                        .and_then(|value| value.parse().ok())
+                        .ok_or_else(|| {
+                            format_error(line, "Expected `best <sample> <step> <genome>`")
+                        })
+                };
+                let (sample_number, step) = (number()?, number()?);
+                Some((
+                    sample_number,
+                    step,
+                    codec.decode(parts.next().unwrap_or(""))?,
+                ))
+            }
+            None => return Err(format_error(line, "Expected `best ...`")),
+        };
+
+        let population_size = parse_field(next_line("population")?, "population")?;
+        let population = (0..population_size)
+            .map(|_| next_line("genome").and_then(|(_, text)| codec.decode(text)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            seed,
+            generation,
+            evaluations,
+            best,
+            population,
+        })
+    }
+}
+
+fn format_error(line: usize, message: impl Into<String>) -> CheckpointError {
+    CheckpointError::Format {
+        line,
+        message: message.into(),
+    }
+}
+
+/// Parse a line of the form `<name> <value>`.
+fn parse_field<T: FromStr>((line, text): (usize, &str), name: &str) -> Result<T, CheckpointError> {
+    text.strip_prefix(name)
+        .and_then(|value| value.strip_prefix(' '))
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| format_error(line, format!("Expected `{name} <value>`")))
+}
+
+#[cfg(test)]
+mod tests {
+    use push::instruction::{FloatInstruction, IntInstruction};
+
+    use super::*;
+
+    #[test]
+    fn bitstring_checkpoint_round_trip() {
+        let bitstring = |bits: &str| BitstringCodec.decode(bits).unwrap();
+        let checkpoint = Checkpoint {
+            seed: 1234,
+            generation: 7,
+            evaluations: 800,
+            best: Some((321, 3, bitstring("1111"))),
+            population: vec![bitstring("0101"), bitstring("1100"), bitstring("")],
+        };
+
+        let path = std::env::temp_dir().join(format!(
+            "course-helpers-checkpoint-test-{}.txt",
+            std::process::id()
+        ));
+        checkpoint.save(&path, &BitstringCodec).unwrap();
+        let loaded = Checkpoint::load(&path, &BitstringCodec).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, checkpoint);
+    }
+
+    #[test]
+    fn plushy_round_trip() {
+        let codec = PlushyCodec::new(vec![
+            IntInstruction::Add.into(),
+            IntInstruction::Subtract.into(),
+            FloatInstruction::Multiply.into(),
+        ]);
+        let plushy = Plushy::new(vec![
+            PushGene::Instruction(FloatInstruction::Multiply.into()),
+            PushGene::Close,
+            PushGene::Instruction(IntInstruction::Add.into()),
+            PushGene::Instruction(IntInstruction::Add.into()),
+            PushGene::Close,
+        ]);
+
+        let text = codec.encode(&plushy).unwrap();
+        assert_eq!(text, "2 ) 0 0 )");
+        assert_eq!(codec.decode(&text).unwrap(), plushy);
+        assert_eq!(codec.decode("").unwrap(), Plushy::new(Vec::new()));
+
+        let unknown = Plushy::new(vec![PushGene::Instruction(IntInstruction::Multiply.into())]);
+        assert!(matches!(
+            codec.encode(&unknown),
+            Err(CheckpointError::Encode(_))
+        ));
+        assert!(matches!(codec.decode("3"), Err(CheckpointError::Genome(_))));
+    }
+}
@@ -0,0 +1,70 @@
+use std::fmt::{self, Debug};
+
+use ec_core::{individual::ec::EcIndividual, operator::selector::Selector};
+use rand::{Rng, RngCore};
+
+use super::Population;
+
+/// A selector of any type, so that the islands in an
+/// [`IslandRun`](super::IslandRun) can each use a different kind of
+/// selection, e.g., tournaments on some islands and lexicase on others.
+///
+/// `ec_core`'s `Selector` can't be used as a trait object (`select` is generic
+/// over the RNG), so this wraps the selector in one that can.
+pub struct DynSelector<G, Sc>(Box<dyn ErasedSelector<G, Sc> + Send + Sync>);
+
+impl<G, Sc> DynSelector<G, Sc> {
+    pub fn new<S>(selector: S) -> Self
+    where
+        S: Selector<Population<G, Sc>> + Send + Sync + 'static,
+        S::Error: std::error::Error + Send + Sync + 'static,
+    {
+        Self(Box::new(selector))
+    }
+}
+
+impl<G, Sc> Debug for DynSelector<G, Sc> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynSelector").finish_non_exhaustive()
+    }
+}
+
+/// The error from whichever selector a [`DynSelector`] wraps.
+#[derive(Debug, thiserror::Error)]
+#[error(transparent)]
+pub struct DynSelectionError(Box<dyn std::error::Error + Send + Sync>);
+
+trait ErasedSelector<G, Sc> {
+    fn select_erased<'pop>(
+        &self,
+        population: &'pop Population<G, Sc>,
+        rng: &mut dyn RngCore,
+    ) -> Result<&'pop EcIndividual<G, Sc>, DynSelectionError>;
+}
+
+impl<G, Sc, S> ErasedSelector<G, Sc> for S
+where
+    S: Selector<Population<G, Sc>>,
+    S::Error: std::error::Error + Send + Sync + 'static,
+{
+    fn select_erased<'pop>(
+        &self,
+        population: &'pop Population<G, Sc>,
+        rng: &mut dyn RngCore,
+    ) -> Result<&'pop EcIndividual<G, Sc>, DynSelectionError> {
+        self.select(population, rng)
+            .map_err(|error| DynSelectionError(Box::new(error)))
+    }
+}
+
+impl<G, Sc> Selector<Population<G, Sc>> for DynSelector<G, Sc> {
+    type Error = DynSelectionError;
+
+    fn select<'pop, R: Rng + ?Sized>(
+        &self,
+        population: &'pop Population<G, Sc>,
+        mut rng: &mut R,
+    ) -> Result<&'pop EcIndividual<G, Sc>, Self::Error> {
+        self.0.select_erased(population, &mut rng)
+    }
+}
@@ -0,0 +1,717 @@
+use std::{
+    any::Any,
+    marker::PhantomData,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Condvar, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use bon::Builder;
+use ec_core::{
+    distributions::collection::ConvertToCollectionGenerator,
+    individual::{ec::WithScorer, scorer::Scorer as IndividualScorer},
+    operator::{mutator::Mutator, recombinator::Recombinator, selector::Selector},
+};
+use flume::{Receiver, RecvTimeoutError, Sender};
+use rand::{distr::Distribution, seq::SliceRandom};
+use std::fmt::Debug;
+
+use super::{Population, Variation};
+use crate::{
+    inspector::Inspector,
+    report::SearchReport,
+    seeding::{derive_seed, seed_or_random, stream_rng},
+    termination::{ProgressTracker, Termination, TerminationReason},
+};
+
+/// Which islands send migrants to which other islands.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Topology {
+    /// Island `i` sends migrants to island `i + 1` (wrapping around).
+    #[default]
+    Ring,
+    /// Every island sends migrants to every other island.
+    FullyConnected,
+    /// The islands are arranged in a new random ring for every migration.
+    Random,
+}
+
+impl Topology {
+    /// The islands that `island` sends migrants to in the given migration.
+    fn targets(self, island: usize, num_islands: usize, migration_seed: u64) -> Vec<usize> {
+        match self {
+            Self::Ring => vec![(island + 1) % num_islands],
+            Self::FullyConnected => (0..num_islands).filter(|&other| other != island).collect(),
+            Self::Random => {
+                // Every island shuffles the same way, so they all agree on the ring.
+                let mut order = (0..num_islands).collect::<Vec<_>>();
+                order.shuffle(&mut stream_rng(migration_seed, 0));
+                let position = order.iter().position(|&other| other == island).unwrap_or(0);
+                vec![order[(position + 1) % num_islands]]
+            }
+        }
+    }
+
+    /// The number of groups of migrants each island receives in a migration.
+    const fn num_incoming(self, num_islands: usize) -> usize {
+        match self {
+            Self::Ring | Self::Random => 1,
+            Self::FullyConnected => num_islands - 1,
+        }
+    }
+}
+
+/// What [`IslandRun::execute`] returns: the usual [`SearchReport`] (covering
+/// all the islands) plus the final population of each island.
+#[derive(Debug, Clone)]
+pub struct IslandRunReport<G, Sc> {
+    pub search: SearchReport<G, Sc>,
+    pub final_islands: Vec<Population<G, Sc>>,
+}
+
+/// An island-model version of [`Run`](super::Run).
+///
+/// There is one island (subpopulation) for each selector in `selectors`, so
+/// `vec![Tournament::of_size::<3>(); 4]` gives four islands that all use the
+/// same kind of selection. The selectors all have the same type, so to give
+/// islands different kinds of selection (e.g., tournaments on some and
+/// lexicase on others), wrap each one in a [`DynSelector`](super::DynSelector).
+/// Each island evolves on its own thread, and
+/// every `migration_interval` generations each island sends copies of its
+/// `num_migrants` best individuals to other islands (over `flume` channels)
+/// according to the `topology`. Migrants replace the worst individuals on the
+/// island that receives them.
+///
+/// Given the same seed, runs that stop at `max_generations` evolve exactly
+/// the same islands and report the same best individual. The samples in each
+/// generation are numbered island by island, as if the islands had taken
+/// turns, so the sample numbers don't depend on which thread gets there first.
+///
+/// If any island's thread panics, the other islands stop at the next
+/// opportunity and the run returns an error.
+#[derive(Builder)]
+pub struct IslandRun<G, GM, Scorer, Sel, Rec, Mut, Ins>
+// You typically wouldn't put all these constraints on the struct itself, instead
+// you'd put them on the `impl` block for the struct. But I'm doing it here to
+// make the constraints more visible and (hopefully) make some of the error
+// messages more helpful for people new to Rust.
+where
+    // The genome type, and a distribution used to generate the initial genomes
+    G: Clone + Send + Sync,
+    GM: Distribution<G> + Sync,
+    Scorer: IndividualScorer<G> + Send + Sync,
+    Scorer::Score: Debug + Clone + Send + Sync + Ord,
+    // Selector, Recombinator, and Mutator
+    Sel: Selector<Population<G, Scorer::Score>> + Send + Sync,
+    Rec: Recombinator<[G; 2], Output = G> + Send + Sync,
+    Mut: Mutator<G> + Send + Sync,
+    Sel::Error: std::error::Error + Send + Sync + 'static,
+    Rec::Error: std::error::Error + Send + Sync + 'static,
+    Mut::Error: std::error::Error + Send + Sync + 'static,
+    // Inspector, whose `on_island_generation` hook is given the island
+    // number, the generation number, and that island's population.
+    Ins: Inspector<G, Scorer::Score> + Send,
+{
+    #[builder(field)]
+    _p: PhantomData<G>,
+
+    genome_maker: GM,
+
+    /// The size of each island's population.
+    #[builder(default = 100)]
+    island_size: usize,
+
+    #[builder(default = usize::MAX)]
+    max_generations: usize,
+
+    /// Additional criteria for stopping the run before `max_generations`.
+    /// These are checked against all the islands together, and once they are
+    /// met every island stops.
+    #[builder(default)]
+    termination: Termination<Scorer::Score>,
+
+    #[builder(default = 10)]
+    migration_interval: usize,
+
+    #[builder(default = 1)]
+    num_migrants: usize,
+
+    #[builder(default)]
+    topology: Topology,
+
+    /// The seed for the random number generators. If this isn't specified a
+    /// random seed is used, which is included in the returned report.
+    seed: Option<u64>,
+
+    scorer: Scorer,
+    selectors: Vec<Sel>,
+    variation: Variation<Rec, Mut>,
+
+    inspector: Ins,
+}
+
+/// Migrants sent between islands, tagged with the island that sent them.
+type Migrants<G, Sc> = (usize, Population<G, Sc>);
+
+impl<G, GM, Scorer, Sel, Rec, Mut, Ins> IslandRun<G, GM, Scorer, Sel, Rec, Mut, Ins>
+where
+    G: Clone + Send + Sync,
+    GM: Distribution<G> + Sync,
+    Scorer: IndividualScorer<G> + Send + Sync,
+    Scorer::Score: Debug + Clone + Send + Sync + Ord,
+    Sel: Selector<Population<G, Scorer::Score>> + Send + Sync,
+    Rec: Recombinator<[G; 2], Output = G> + Send + Sync,
+    Mut: Mutator<G> + Send + Sync,
+    Sel::Error: std::error::Error + Send + Sync + 'static,
+    Rec::Error: std::error::Error + Send + Sync + 'static,
+    Mut::Error: std::error::Error + Send + Sync + 'static,
+    Ins: Inspector<G, Scorer::Score> + Send,
+{
+    /// Evolve all the islands until `max_generations` is reached or the
+    /// `termination` criteria are met.
+    ///
+    /// # Errors
+    ///
+    /// This can return an error if there are no selectors (and hence no islands),
+    /// if the migration interval is zero, if making a new child on any island
+    /// fails, or if any island's thread panics.
+    pub fn execute(mut self) -> anyhow::Result<IslandRunReport<G, Scorer::Score>> {
+        let num_islands = self.selectors.len();
+        anyhow::ensure!(num_islands > 0, "An island run needs at least one selector");
+        anyhow::ensure!(
+            self.migration_interval > 0,
+            "The migration interval has to be positive"
+        );
+
+        let seed = seed_or_random(self.seed);
+        self.inspector.on_start();
+        let (senders, receivers): (Vec<_>, Vec<_>) =
+            (0..num_islands).map(|_| flume::unbounded()).unzip();
+        let shared = Mutex::new((&mut self.inspector, ProgressTracker::new(seed), None));
+        let stopped = AtomicBool::new(false);
+        let barrier = IslandBarrier::new(num_islands);
+
+        let context = IslandContext {
+            genome_maker: &self.genome_maker,
+            scorer: &self.scorer,
+            variation: &self.variation,
+            island_size: self.island_size,
+            max_generations: self.max_generations,
+            termination: &self.termination,
+            migration_interval: self.migration_interval,
+            num_migrants: self.num_migrants,
+            topology: self.topology,
+            num_islands,
+            seed,
+            senders: &senders,
+            shared: &shared,
+            stopped: &stopped,
+            barrier: &barrier,
+        };
+        let context = &context;
+        let results = thread::scope(|scope| {
+            let handles = self
+                .selectors
+                .iter()
+                .zip(receivers)
+                .enumerate()
+                .map(|(island, (selector, receiver))| {
+                    scope.spawn(move || {
+                        // If this island panics we have to abort the run, or the
+                        // other islands would wait forever for it at the next
+                        // migration.
+                        panic::catch_unwind(AssertUnwindSafe(|| {
+                            context.evolve_island(island, selector, &receiver)
+                        }))
+                        .unwrap_or_else(|payload| {
+                            context.barrier.abort();
+                            context.stopped.store(true, Ordering::SeqCst);
+                            Err(anyhow::anyhow!(
+                                "Island {island} panicked: {}",
+                                panic_message(payload.as_ref())
+                            ))
+                        })
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|_| Err(anyhow::anyhow!("An island thread panicked")))
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let final_islands = results.into_iter().collect::<anyhow::Result<Vec<_>>>()?;
+        // No island panicked (or we would have returned above), so the lock
+        // can't be poisoned.
+        let (inspector, tracker, termination_reason) = shared
+            .into_inner()
+            .expect("No island thread panicked while holding the lock");
+        let search = tracker.into_report(termination_reason.unwrap_or(TerminationReason::MaxSteps));
+        inspector.on_finish(&search);
+        Ok(IslandRunReport {
+            search,
+            final_islands,
+        })
+    }
+}
+
+/// Everything an island's thread needs to borrow from the [`IslandRun`].
+struct IslandContext<'a, 'ins, G, GM, Scorer, Rec, Mut, Ins>
+where
+    Scorer: IndividualScorer<G>,
+{
+    genome_maker: &'a GM,
+    scorer: &'a Scorer,
+    variation: &'a Variation<Rec, Mut>,
+    island_size: usize,
+    max_generations: usize,
+    termination: &'a Termination<Scorer::Score>,
+    migration_interval: usize,
+    num_migrants: usize,
+    topology: Topology,
+    num_islands: usize,
+    seed: u64,
+    senders: &'a [Sender<Migrants<G, Scorer::Score>>],
+    #[expect(
+        clippy::type_complexity,
+        reason = "This is only used here, and a type alias would just hide what's shared."
+    )]
+    shared: &'a Mutex<(
+        &'ins mut Ins,
+        ProgressTracker<G, Scorer::Score>,
+        Option<TerminationReason>,
+    )>,
+    stopped: &'a AtomicBool,
+    barrier: &'a IslandBarrier,
+}
+
+impl<G, GM, Scorer, Rec, Mut, Ins> IslandContext<'_, '_, G, GM, Scorer, Rec, Mut, Ins>
+where
+    G: Clone + Send + Sync,
+    GM: Distribution<G> + Sync,
+    Scorer: IndividualScorer<G> + Send + Sync,
+    Scorer::Score: Debug + Clone + Send + Sync + Ord,
+    Rec: Recombinator<[G; 2], Output = G> + Send + Sync,
+    Mut: Mutator<G> + Send + Sync,
+    Rec::Error: std::error::Error + Send + Sync + 'static,
+    Mut::Error: std::error::Error + Send + Sync + 'static,
+    Ins: Inspector<G, Scorer::Score> + Send,
+{
+    /// Evolve one island, returning its final population.
+    ///
+    /// Every island takes part in every migration, even if it has stopped
+    /// evolving (because of an error or because the termination criteria were
+    /// met), and then all the islands wait for each other before deciding
+    /// whether to keep going. That way no island is ever left waiting for
+    /// migrants that will never arrive. The exception is when another island
+    /// has panicked, in which case the run is aborted and we stop waiting.
+    fn evolve_island<Sel>(
+        &self,
+        island: usize,
+        selector: &Sel,
+        receiver: &Receiver<Migrants<G, Scorer::Score>>,
+    ) -> anyhow::Result<Population<G, Scorer::Score>>
+    where
+        Sel: Selector<Population<G, Scorer::Score>> + Sync,
+        Sel::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let island_seed = derive_seed(self.seed, island as u64);
+        let mut population: Population<G, Scorer::Score> = self
+            .genome_maker
+            .with_scorer(self.scorer)
+            .into_collection_generator(self.island_size)
+            .sample(&mut stream_rng(island_seed, 0));
+        self.record(island, 0, &population);
+
+        let mut generation = 0;
+        let mut error = None;
+        let mut migrants = Vec::new();
+        for migration_number in 0.. {
+            for _ in 0..self.migration_interval {
+                if generation >= self.max_generations || self.stopped.load(Ordering::SeqCst) {
+                    break;
+                }
+                // Migrants only join the island when it's about to breed, so
+                // if it stops first its final population is still the last
+                // one it recorded.
+                replace_worst(&mut population, std::mem::take(&mut migrants));
+                generation += 1;
+                let generation_seed = derive_seed(island_seed, generation as u64);
+                // Each island already has its own thread, so we make its
+                // children serially.
+                let children = self.variation.make_children(
+                    selector,
+                    self.scorer,
+                    &population,
+                    generation_seed,
+                    0..population.len(),
+                    false,
+                );
+                match children {
+                    Ok(children) => population = children,
+                    Err(child_error) => {
+                        error = Some(child_error);
+                        self.stopped.store(true, Ordering::SeqCst);
+                        break;
+                    }
+                }
+                self.record(island, generation, &population);
+            }
+
+            // Everyone has to get here before anyone checks whether to stop,
+            // and everyone has to check before anyone starts the next epoch
+            // (and possibly sets `stopped`), so all the islands make the same
+            // decision, and either all of them migrate or none of them do.
+            // All the islands reach `max_generations` in the same epoch, so we
+            // don't need `stopped` for that.
+            if !self.barrier.wait() {
+                break;
+            }
+            let stop = self.stopped.load(Ordering::SeqCst) || generation >= self.max_generations;
+            if !self.barrier.wait() || stop {
+                break;
+            }
+
+            if self.num_islands > 1 {
+                migrants = self.migrate(island, migration_number, &population, receiver);
+            }
+        }
+
+        error.map_or(Ok(population), Err)
+    }
+
+    /// Record the scores of a new generation, pass it to the inspector, and
+    /// check the termination criteria.
+    fn record(&self, island: usize, generation: usize, population: &Population<G, Scorer::Score>) {
+        let mut shared = self
+            .shared
+            .lock()
+            .expect("An island thread panicked while holding the lock");
+        let (inspector, tracker, termination_reason) = &mut *shared;
+        tracker.advance_to(generation);
+        let first_sample = (generation * self.num_islands + island) * self.island_size;
+        for (index, individual) in population.iter().enumerate() {
+            tracker.record_and_inspect_at(
+                generation,
+                first_sample + index,
+                &individual.genome,
+                &individual.test_results,
+                inspector,
+            );
+        }
+        inspector.on_island_generation(island, generation, population);
+        if termination_reason.is_none() {
+            if let Some(reason) = tracker.check(self.termination) {
+                *termination_reason = Some(reason);
+                self.stopped.store(true, Ordering::SeqCst);
+            }
+        }
+    }
+
+    /// Send copies of our best individuals to the target islands, and return
+    /// the migrants we receive.
+    fn migrate(
+        &self,
+        island: usize,
+        migration_number: usize,
+        population: &Population<G, Scorer::Score>,
+        receiver: &Receiver<Migrants<G, Scorer::Score>>,
+    ) -> Population<G, Scorer::Score> {
+        let migration_seed = derive_seed(self.seed, migration_number as u64);
+        let migrants = super::elites(population, self.num_migrants);
+        for target in self
+            .topology
+            .targets(island, self.num_islands, migration_seed)
+        {
+            // This can only fail if the target island's thread has panicked
+            // (dropping its receiver), in which case the run is being aborted.
+            let _ = self.senders[target].send((island, migrants.clone()));
+        }
+
+        let mut incoming = (0..self.topology.num_incoming(self.num_islands))
+            .map_while(|_| self.receive(receiver))
+            .collect::<Vec<_>>();
+        // Migrants can arrive in any order, so we sort them by where they came
+        // from to keep seeded runs reproducible.
+        incoming.sort_by_key(|(source, _)| *source);
+        incoming
+            .into_iter()
+            .flat_map(|(_, migrants)| migrants)
+            .collect()
+    }
+
+    /// Wait for the next group of migrants, or return `None` if the run is
+    /// aborted first. The main thread holds on to every `Sender` until the
+    /// run is over, so `recv` on its own would never notice that the island
+    /// we're waiting for has died.
+    fn receive(
+        &self,
+        receiver: &Receiver<Migrants<G, Scorer::Score>>,
+    ) -> Option<Migrants<G, Scorer::Score>> {
+        loop {
+            match receiver.recv_timeout(ABORT_CHECK_INTERVAL) {
+                Ok(migrants) => return Some(migrants),
+                Err(RecvTimeoutError::Timeout) if !self.barrier.is_aborted() => {}
+                Err(_) => return None,
+            }
+        }
+    }
+}
+
+/// Replace the worst individuals in `population` with the `migrants`.
+fn replace_worst<G, Sc: Ord>(population: &mut Population<G, Sc>, migrants: Population<G, Sc>) {
+    if migrants.is_empty() {
+        return;
+    }
+    let mut worst_first = (0..population.len()).collect::<Vec<_>>();
+    worst_first.sort_by(|&first, &second| {
+        population[first]
+            .test_results
+            .cmp(&population[second].test_results)
+    });
+    for (index, migrant) in worst_first.into_iter().zip(migrants) {
+        population[index] = migrant;
+    }
+}
+
+/// How often an island that's waiting for migrants checks whether the run
+/// has been aborted.
+const ABORT_CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Like [`std::sync::Barrier`], except that it can be aborted, releasing
+/// every island that's waiting (or will wait) on it.
+struct IslandBarrier {
+    num_islands: usize,
+    state: Mutex<BarrierState>,
+    condvar: Condvar,
+}
+
+#[derive(Default)]
+struct BarrierState {
+    num_waiting: usize,
+    // The number of times every island has reached the barrier, so waiting
+    // islands can tell when they've been released.
+    num_releases: usize,
+    aborted: bool,
+}
+
+impl IslandBarrier {
+    fn new(num_islands: usize) -> Self {
+        Self {
+            num_islands,
+            state: Mutex::default(),
+            condvar: Condvar::new(),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BarrierState> {
+        // We never panic while holding the lock, but if we somehow did the
+        // state would still be consistent.
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Wait until every island has reached the barrier, returning `false`
+    /// (right away, if need be) if the run has been aborted.
+    fn wait(&self) -> bool {
+        let mut state = self.lock();
+        if state.aborted {
+            return false;
+        }
+        state.num_waiting += 1;
+        if state.num_waiting == self.num_islands {
+            state.num_waiting = 0;
+            state.num_releases += 1;
+            self.condvar.notify_all();
+            return true;
+        }
+        let release = state.num_releases;
+        while state.num_releases == release && !state.aborted {
+            state = self
+                .condvar
+                .wait(state)
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+        }
+        state.num_releases != release
+    }
+
+    fn abort(&self) {
+        self.lock().aborted = true;
+        self.condvar.notify_all();
+    }
+
+    fn is_aborted(&self) -> bool {
+        self.lock().aborted
+    }
+}
+
+/// The message from a panic, if it has one.
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("(no message)")
+}
+
+#[cfg(test)]
+mod tests {
+    use ec_core::{
+        individual::scorer::FnScorer, operator::selector::tournament::Tournament,
+        test_results::Score,
+    };
+    use ec_linear::{
+        genome::bitstring::Bitstring, mutator::with_one_over_length::WithOneOverLength,
+    };
+
+    use std::{collections::BTreeMap, sync::atomic::AtomicUsize};
+
+    use ec_core::operator::selector::best::Best;
+
+    use super::*;
+    use crate::{
+        ec_run::{random_bitstrings, DynSelector, NoMutation},
+        test_support::count_ones,
+    };
+
+    /// Remembers the last population each island showed the inspector.
+    #[derive(Default)]
+    struct LastIslands(BTreeMap<usize, Population<Bitstring, Score<usize>>>);
+
+    impl Inspector<Bitstring, Score<usize>> for LastIslands {
+        fn on_island_generation(
+            &mut self,
+            island: usize,
+            _generation: usize,
+            population: &Population<Bitstring, Score<usize>>,
+        ) {
+            self.0.insert(island, population.clone());
+        }
+    }
+
+    fn island_run(
+        topology: Topology,
+        termination: Termination<Score<usize>>,
+    ) -> IslandRunReport<Bitstring, Score<usize>> {
+        let mut last_islands = LastIslands::default();
+        let report = IslandRun::builder()
+            .genome_maker(random_bitstrings(32, 0.5).unwrap())
+            .island_size(10)
+            .max_generations(12)
+            .termination(termination)
+            .migration_interval(4)
+            .num_migrants(2)
+            .topology(topology)
+            .seed(5)
+            .scorer(FnScorer(count_ones))
+            .selectors(vec![Tournament::of_size::<2>(); 3])
+            .variation(Variation::mutation(WithOneOverLength))
+            .inspector(&mut last_islands)
+            .build()
+            .execute()
+            .unwrap();
+        // Migrants that arrive after an island's last generation don't end
+        // up in its final population.
+        assert_eq!(
+            report.final_islands,
+            last_islands.0.into_values().collect::<Vec<_>>()
+        );
+        report
+    }
+
+    #[test]
+    fn seeded_island_runs_are_reproducible() {
+        for topology in [Topology::Ring, Topology::FullyConnected, Topology::Random] {
+            let first = island_run(topology, Termination::Never);
+            let second = island_run(topology, Termination::Never);
+            assert_eq!(first.final_islands.len(), 3);
+            assert_eq!(first.final_islands, second.final_islands);
+            assert_eq!(first.search.steps, 12);
+            assert_eq!(first.search.evaluations, 3 * 10 * 13);
+            let best = |report: &IslandRunReport<Bitstring, Score<usize>>| {
+                report
+                    .search
+                    .best
+                    .as_ref()
+                    .map(|best| (best.sample_number, best.step, best.genome.clone()))
+            };
+            assert_eq!(best(&first), best(&second));
+
+            // Stopping part way through an epoch.
+            let stopped = island_run(topology, Termination::MaxSteps(6));
+            assert_eq!(
+                stopped.search.termination_reason,
+                TerminationReason::MaxSteps
+            );
+        }
+    }
+
+    #[test]
+    fn islands_can_use_different_selectors() {
+        let report = IslandRun::builder()
+            .genome_maker(random_bitstrings(32, 0.5).unwrap())
+            .island_size(10)
+            .max_generations(1)
+            .seed(5)
+            .scorer(FnScorer(count_ones))
+            .selectors(vec![
+                DynSelector::new(Tournament::of_size::<2>()),
+                DynSelector::new(Best),
+            ])
+            .variation(Variation::mutation(NoMutation))
+            .inspector(())
+            .build()
+            .execute()
+            .unwrap();
+        let num_distinct_scores = |population: &Population<Bitstring, Score<usize>>| {
+            let mut scores = population
+                .iter()
+                .map(|individual| &individual.test_results)
+                .collect::<Vec<_>>();
+            scores.sort();
+            scores.dedup();
+            scores.len()
+        };
+        // Without mutation, the island that always selects the best
+        // individual is left with nothing but copies of it.
+        assert!(num_distinct_scores(&report.final_islands[0]) > 1);
+        assert_eq!(num_distinct_scores(&report.final_islands[1]), 1);
+    }
+
+    #[test]
+    fn a_panicking_island_aborts_the_run() {
+        let num_scored = AtomicUsize::new(0);
+        let result = IslandRun::builder()
+            .genome_maker(random_bitstrings(32, 0.5).unwrap())
+            .island_size(10)
+            .max_generations(1_000)
+            .migration_interval(4)
+            .seed(5)
+            .scorer(FnScorer(|bitstring: &Bitstring| {
+                assert!(
+                    num_scored.fetch_add(1, Ordering::SeqCst) < 100,
+                    "Scored too many bitstrings"
+                );
+                count_ones(bitstring)
+            }))
+            .selectors(vec![Tournament::of_size::<2>(); 3])
+            .variation(Variation::mutation(WithOneOverLength))
+            .inspector(())
+            .build()
+            .execute();
+        let error = result.unwrap_err().to_string();
+        assert!(
+            error.contains("panicked: Scored too many bitstrings"),
+            "{error}"
+        );
+    }
+}
@@ -0,0 +1,836 @@
+mod checkpoint;
+mod dyn_selector;
+mod island;
+mod replacement;
+mod stepper;
+mod variation;
+
+pub use checkpoint::*;
+pub use dyn_selector::*;
+pub use island::*;
+pub use replacement::*;
+pub use stepper::*;
+pub use variation::*;
+
+use crate::{
+    inspector::Inspector,
+    report::{BestFound, SearchReport},
+    seeding::{derive_seed, seed_or_random, stream_rng},
+    termination::{ProgressTracker, Termination},
+};
+use anyhow::ensure;
+use bon::Builder;
+use ec_core::{
+    distributions::collection::{CollectionGenerator, ConvertToCollectionGenerator},
+    individual::{
+        ec::{EcIndividual, WithScorer},
+        scorer::Scorer as IndividualScorer,
+    },
+    operator::{mutator::Mutator, recombinator::Recombinator, selector::Selector},
+};
+use rand::{
+    distr::{Bernoulli, BernoulliError, Distribution},
+    rngs::StdRng,
+};
+use std::{fmt::Debug, marker::PhantomData};
+
+/// A population of (scored) individuals with genomes of type `G`.
+pub type Population<G, Sc> = Vec<EcIndividual<G, Sc>>;
+
+/// What [`Run::execute`] returns: the usual [`SearchReport`] plus the final
+/// population.
+#[derive(Debug, Clone)]
+pub struct RunReport<G, Sc> {
+    pub search: SearchReport<G, Sc>,
+    pub final_population: Population<G, Sc>,
+}
+
+/// A generational evolutionary run over genomes of type `G`.
+///
+/// The initial population is sampled from `genome_maker`, so the same `Run` can
+/// drive (for example) a bitstring GA, where the genome maker might be
+/// [`random_bitstrings`], or PushGP on `Plushy` genomes, where it would be a
+/// gene generator converted with `to_collection_generator(max_initial_instructions)`.
+/// Any `initial_genomes` are added to the initial population as is, and the rest
+/// of the population is filled in by sampling from `genome_maker`.
+///
+/// New individuals are made by selecting parents with `selector` and then
+/// applying the [`Variation`], which can be recombination, mutation, or both.
+#[derive(Builder)]
+pub struct Run<G, GM, Scorer, Sel, Rec, Mut, Ins>
+// You typically wouldn't put all these constraints on the struct itself, instead
+// you'd put them on the `impl` block for the struct. But I'm doing it here to
+// make the constraints more visible and (hopefully) make some of the error
+// messages more helpful for people new to Rust.
+where
+    // The genome type, and a distribution used to generate the initial genomes
+    G: Clone + Send + Sync,
+    GM: Distribution<G>,
+    Scorer: IndividualScorer<G> + Send + Sync,
+    Scorer::Score: Debug + Clone + Send + Sync + Ord,
+    // Selector, Recombinator, and Mutator
+    Sel: Selector<Population<G, Scorer::Score>> + Send + Sync,
+    Rec: Recombinator<[G; 2], Output = G> + Send + Sync,
+    Mut: Mutator<G> + Send + Sync,
+    // All associated error types have to implement `std::error::Error`.
+    // They also have to be `Send` and `Sync` if we're using parallel evaluation
+    // so that errors can propagate across threads.
+    // They also have to be bounded by `'static` lifetimes so they can be held to the
+    // end of the program as necessary.
+    Sel::Error: std::error::Error + Send + Sync + 'static,
+    Rec::Error: std::error::Error + Send + Sync + 'static,
+    Mut::Error: std::error::Error + Send + Sync + 'static,
+    // Inspector
+    Ins: Inspector<G, Scorer::Score>,
+{
+    // We need `PhantomData` because `Run` depends on the genome type `G` but
+    // doesn't actually contain an instance of it.
+    #[builder(field)]
+    _p: PhantomData<G>,
+
+    genome_maker: GM,
+
+    /// Genomes to "seed" the initial population with, e.g., known good solutions
+    /// to a related problem. There can't be more of these than `population_size`.
+    #[builder(default)]
+    initial_genomes: Vec<G>,
+
+    #[builder(default = 100)]
+    population_size: usize,
+
+    /// The number of best individuals to copy unchanged into each new
+    /// generation, so the best individual in the population is never lost.
+    /// This can't be larger than `population_size`.
+    #[builder(default = 0)]
+    num_elites: usize,
+
+    #[builder(default = usize::MAX)]
+    max_generations: usize,
+
+    /// Additional criteria for stopping the run before `max_generations`.
+    #[builder(default)]
+    termination: Termination<Scorer::Score>,
+
+    #[builder(default = true)]
+    parallel_evaluation: bool,
+
+    /// Whether to replace the whole population each generation (the default)
+    /// or a few individuals at a time.
+    #[builder(default)]
+    replacement: Replacement,
+
+    /// The seed for the random number generators. If this isn't specified a
+    /// random seed is used, which is included in the returned report. Given the
+    /// same seed, serial and parallel evaluation give exactly the same run.
+    seed: Option<u64>,
+
+    /// Periodically save the state of the run so it can be resumed later.
+    checkpointing: Option<Checkpointing<G>>,
+
+    /// Pick up from a saved checkpoint instead of starting a new run. The
+    /// run continues with the seed from the checkpoint, so the resumed run
+    /// continues exactly as the original run would have. Setting a different
+    /// `seed`, any `initial_genomes`, or a `population_size` that doesn't
+    /// match the checkpoint is an error.
+    resume_from: Option<Checkpoint<G>>,
+
+    scorer: Scorer,
+    selector: Sel,
+    variation: Variation<Rec, Mut>,
+
+    inspector: Ins,
+}
+
+impl<G, GM, Scorer, Sel, Rec, Mut, Ins> Run<G, GM, Scorer, Sel, Rec, Mut, Ins>
+where
+    // The genome type, and a distribution used to generate the initial genomes
+    G: Clone + Send + Sync,
+    GM: Distribution<G>,
+    Scorer: IndividualScorer<G> + Send + Sync,
+    Scorer::Score: Debug + Clone + Send + Sync + Ord,
+    // Selector, Recombinator, and Mutator
+    Sel: Selector<Population<G, Scorer::Score>> + Send + Sync,
+    Rec: Recombinator<[G; 2], Output = G> + Send + Sync,
+    Mut: Mutator<G> + Send + Sync,
+    // All associated error types have to implement `std::error::Error`.
+    // They also have to be `Send` and `Sync` if we're using parallel evaluation
+    // so that errors can propagate across threads.
+    // They also have to be bounded by `'static` lifetimes so they can be held to the
+    // end of the program as necessary.
+    Sel::Error: std::error::Error + Send + Sync + 'static,
+    Rec::Error: std::error::Error + Send + Sync + 'static,
+    Mut::Error: std::error::Error + Send + Sync + 'static,
+    // Inspector
+    Ins: Inspector<G, Scorer::Score>,
+{
+    /// Evolve the population until `max_generations` is reached or the
+    /// `termination` criteria are met, returning a [`RunReport`] with the
+    /// best individual seen during the run and the final population.
+    ///
+    /// # Errors
+    ///
+    /// This can return an error if:
+    ///    - There are more `initial_genomes` or `num_elites` than `population_size`
+    ///    - The population is empty at some point, so selection fails (this should
+    ///      never happen)
+    ///    - Creating a new generation fails, probably in creating or scoring new individuals
+    ///    - Saving a checkpoint fails
+    pub fn execute(self) -> anyhow::Result<RunReport<G, Scorer::Score>> {
+        let mut stepper = self.stepper()?;
+        while stepper.step()? {}
+        Ok(stepper.finish())
+    }
+
+    /// Set up the initial population, and return a [`RunStepper`] that the
+    /// caller can use to run the evolution one generation at a time.
+    ///
+    /// # Errors
+    ///
+    /// This can return an error if there are more `initial_genomes` or
+    /// `num_elites` than `population_size`, if the checkpoint interval is
+    /// zero, if creating the initial population fails, or if `resume_from` is
+    /// set and the checkpoint doesn't match the run's settings.
+    pub fn stepper(mut self) -> anyhow::Result<RunStepper<G, GM, Scorer, Sel, Rec, Mut, Ins>> {
+        ensure!(
+            self.num_elites <= self.population_size,
+            "There are {} elites, but the population size is only {}",
+            self.num_elites,
+            self.population_size
+        );
+
+        if let Replacement::SteadyState {
+            children_per_step, ..
+        } = self.replacement
+        {
+            ensure!(
+                children_per_step > 0,
+                "A steady-state run has to make at least one child per step"
+            );
+            ensure!(
+                self.num_elites < self.population_size,
+                "A steady-state run needs some individuals that aren't elites to replace"
+            );
+        }
+
+        if let Some(checkpointing) = &self.checkpointing {
+            ensure!(
+                checkpointing.every_n_generations > 0,
+                "The checkpoint interval has to be positive"
+            );
+        }
+
+        // We only call `on_start` once nothing else can fail, so inspectors
+        // don't see the start of a run that never happens.
+        let (tracker, population) = match self.resume_from.take() {
+            Some(checkpoint) => {
+                let resumed = self.resume(checkpoint)?;
+                self.inspector.on_start();
+                resumed
+            }
+            None => {
+                let seed = seed_or_random(self.seed);
+
+                // Create the initial population for the run
+                let population = self.initial_population(&mut stream_rng(seed, 0))?;
+                self.inspector.on_start();
+                let mut tracker = ProgressTracker::new(seed);
+                self.record_scores(&mut tracker, &population);
+                (tracker, population)
+            }
+        };
+        self.inspector.on_generation(tracker.steps(), &population);
+
+        Ok(RunStepper {
+            run: self,
+            tracker,
+            population,
+            termination_reason: None,
+        })
+    }
+
+    /// Rebuild the state of a run from a checkpoint, rescoring the population
+    /// and best genome since scores aren't saved in checkpoints.
+    #[expect(
+        clippy::type_complexity,
+        reason = "This is only used in `stepper`, which unpacks it immediately."
+    )]
+    fn resume(
+        &self,
+        checkpoint: Checkpoint<G>,
+    ) -> anyhow::Result<(
+        ProgressTracker<G, Scorer::Score>,
+        Population<G, Scorer::Score>,
+    )> {
+        ensure!(
+            self.seed.is_none_or(|seed| seed == checkpoint.seed),
+            "The run's seed ({:?}) doesn't match the checkpoint's seed ({})",
+            self.seed,
+            checkpoint.seed
+        );
+        ensure!(
+            self.initial_genomes.is_empty(),
+            "A resumed run can't have initial genomes, since its population comes from the checkpoint"
+        );
+        ensure!(
+            checkpoint.population.len() == self.population_size,
+            "The checkpoint has {} genomes, but the population size is {}",
+            checkpoint.population.len(),
+            self.population_size
+        );
+
+        let best = checkpoint
+            .best
+            .map(|(sample_number, step, genome)| BestFound {
+                score: self.scorer.score(&genome),
+                genome,
+                sample_number,
+                step,
+            });
+        let tracker = ProgressTracker::resume(
+            checkpoint.seed,
+            checkpoint.generation,
+            checkpoint.evaluations,
+            best,
+        );
+        let population = checkpoint
+            .population
+            .into_iter()
+            .map(|genome| {
+                let score = self.scorer.score(&genome);
+                EcIndividual::new(genome, score)
+            })
+            .collect();
+        Ok((tracker, population))
+    }
+
+    /// Save a checkpoint if checkpointing is turned on and it's time to save one.
+    fn save_checkpoint(
+        &self,
+        tracker: &ProgressTracker<G, Scorer::Score>,
+        population: &[EcIndividual<G, Scorer::Score>],
+    ) -> Result<(), CheckpointError> {
+        let Some(checkpointing) = &self.checkpointing else {
+            return Ok(());
+        };
+        if tracker.steps() % checkpointing.every_n_generations != 0 {
+            return Ok(());
+        }
+        let checkpoint = Checkpoint {
+            seed: tracker.seed(),
+            generation: tracker.steps(),
+            evaluations: tracker.evaluations(),
+            best: tracker
+                .best()
+                .map(|best| (best.sample_number, best.step, best.genome.clone())),
+            population: population
+                .iter()
+                .map(|individual| individual.genome.clone())
+                .collect(),
+        };
+        checkpoint.save(&checkpointing.path, checkpointing.codec.as_ref())
+    }
+
+    fn record_scores(
+        &mut self,
+        tracker: &mut ProgressTracker<G, Scorer::Score>,
+        population: &[EcIndividual<G, Scorer::Score>],
+    ) {
+        for individual in population {
+            tracker.record_and_inspect(
+                tracker.evaluations(),
+                &individual.genome,
+                &individual.test_results,
+                &mut self.inspector,
+            );
+        }
+    }
+
+    /// Make a new population (of the same size) from `population`. The first
+    /// `num_elites` individuals are copies of the best individuals in
+    /// `population`, and the rest are new children.
+    ///
+    /// Each child gets its own RNG, derived from `generation_seed` and the
+    /// child's position in the new population, so serial and parallel
+    /// evaluation make exactly the same children.
+    fn next_generation(
+        &self,
+        population: &Population<G, Scorer::Score>,
+        generation_seed: u64,
+    ) -> anyhow::Result<Population<G, Scorer::Score>> {
+        let children = self.variation.make_children(
+            &self.selector,
+            &self.scorer,
+            population,
+            generation_seed,
+            self.num_elites..population.len(),
+            self.parallel_evaluation,
+        )?;
+
+        Ok(elites(population, self.num_elites)
+            .into_iter()
+            .chain(children)
+            .collect())
+    }
+
+    /// Make "population-size" steady-state births in `population`, in steps
+    /// of (up to) `children_per_step` children. Each step's children are all
+    /// made from the population as it was at the start of the step, and then
+    /// each of them replaces a `victim` (never one of the elites). Returns all
+    /// the children, in the order they were born, so they can be recorded.
+    ///
+    /// Like in [`Run::next_generation`], each child gets its own RNG (and each
+    /// step gets one more for choosing victims), so serial and parallel
+    /// evaluation give the same results.
+    fn steady_state_generation(
+        &self,
+        population: &mut Population<G, Scorer::Score>,
+        generation_seed: u64,
+        children_per_step: usize,
+        victim: Victim,
+    ) -> anyhow::Result<Population<G, Scorer::Score>> {
+        let mut births = Vec::with_capacity(population.len());
+        let mut step_number = 0;
+        while births.len() < population.len() {
+            let num_children = children_per_step.min(population.len() - births.len());
+            let step_seed = derive_seed(generation_seed, step_number);
+            let children = self.variation.make_children(
+                &self.selector,
+                &self.scorer,
+                population,
+                step_seed,
+                0..num_children,
+                self.parallel_evaluation,
+            )?;
+
+            // Children only ever replace unprotected individuals, so the
+            // protected ones stay put for the whole step.
+            let candidates = unprotected(population, self.num_elites);
+            let mut victim_rng = stream_rng(step_seed, num_children);
+            for child in children {
+                let index = victim.choose(population, &candidates, &mut victim_rng)?;
+                population[index] = child.clone();
+                births.push(child);
+            }
+            step_number += 1;
+        }
+        Ok(births)
+    }
+
+    fn initial_population(&self, rng: &mut StdRng) -> anyhow::Result<Population<G, Scorer::Score>> {
+        let num_seeded = self.initial_genomes.len();
+        ensure!(
+            num_seeded <= self.population_size,
+            "There are {num_seeded} initial genomes, but the population size is only {}",
+            self.population_size
+        );
+
+        let seeded = self.initial_genomes.iter().map(|genome| {
+            let score = self.scorer.score(genome);
+            EcIndividual::new(genome.clone(), score)
+        });
+
+        let sampled: Vec<_> =
+            // Generate genomes using the (borrowed) genome maker
+            (&self.genome_maker)
+            // Adds a scorer to each genome, creating an `Individual`
+            .with_scorer(&self.scorer)
+            // Create a `Population` of `Individual`s to fill out the rest of the
+            // population after the seeded individuals
+            .into_collection_generator(self.population_size - num_seeded)
+            // Actually sample the distribution to get the initial population.
+            .sample(rng);
+
+        Ok(seeded.chain(sampled).collect())
+    }
+}
+
+/// Clones of the `num_elites` best individuals in `population`, best first.
+///
+/// Ties are broken by position in the population so that elitism doesn't
+/// introduce any nondeterminism into (seeded) runs.
+fn elites<G, Sc>(population: &[EcIndividual<G, Sc>], num_elites: usize) -> Vec<EcIndividual<G, Sc>>
+where
+    G: Clone,
+    Sc: Ord + Clone,
+{
+    if num_elites == 0 {
+        return Vec::new();
+    }
+    let mut ranked = population.iter().collect::<Vec<_>>();
+    // `sort_by` is stable, so equally good individuals stay in population order.
+    ranked.sort_by(|first, second| second.test_results.cmp(&first.test_results));
+    ranked.into_iter().take(num_elites).cloned().collect()
+}
+
+/// A genome maker for [`Run`] that generates `Bitstring`s of length `bit_length`
+/// where each bit is `true` with the given `probability`.
+///
+/// A small probability creates initial bitstrings that are mostly `false`,
+/// which can be a poor (or at least biased) starting point for some problems,
+/// so 0.5 is a reasonable default.
+///
+/// # Errors
+///
+/// This returns an error if `probability` isn't in the range `[0, 1]`.
+pub fn random_bitstrings(
+    bit_length: usize,
+    probability: f64,
+) -> Result<CollectionGenerator<Bernoulli>, BernoulliError> {
+    Ok(Bernoulli::new(probability)?.into_collection_generator(bit_length))
+}
+
+#[cfg(test)]
+mod tests {
+    use ec_core::{
+        individual::scorer::FnScorer,
+        operator::selector::tournament::Tournament,
+        test_results::{Error, Score},
+    };
+    use ec_linear::{
+        genome::bitstring::Bitstring, mutator::with_one_over_length::WithOneOverLength,
+    };
+    use push::{
+        genome::plushy::{Plushy, PushGene},
+        instruction::{FloatInstruction, IntInstruction, PushInstruction},
+    };
+    use rand::distr::Uniform;
+
+    use super::*;
+    use crate::inspector::on_generation;
+    use crate::test_support::count_ones;
+
+    fn run_report(parallel_evaluation: bool) -> RunReport<Bitstring, Score<usize>> {
+        let mut best_scores = Vec::new();
+        let report = Run::builder()
+            .genome_maker(random_bitstrings(32, 0.5).unwrap())
+            .population_size(20)
+            .num_elites(1)
+            .max_generations(10)
+            .parallel_evaluation(parallel_evaluation)
+            .seed(17)
+            .scorer(FnScorer(count_ones))
+            .selector(Tournament::of_size::<2>())
+            .variation(Variation::mutation(WithOneOverLength))
+            .inspector(on_generation(
+                |_, population: &Population<Bitstring, Score<usize>>| {
+                    best_scores.push(population.iter().map(|ind| ind.test_results.0).max());
+                },
+            ))
+            .build()
+            .execute()
+            .unwrap();
+        assert!(best_scores.is_sorted());
+        report
+    }
+
+    #[test]
+    fn elitism_never_loses_the_best() {
+        let serial = run_report(false);
+        let parallel = run_report(true);
+        assert_eq!(serial.search.steps, 10);
+        assert_eq!(serial.final_population, parallel.final_population);
+    }
+
+    fn seeded_run(
+        initial_genomes: Vec<Bitstring>,
+    ) -> anyhow::Result<Population<Bitstring, Score<usize>>> {
+        let mut initial_population = Vec::new();
+        Run::builder()
+            .genome_maker(random_bitstrings(8, 0.5).unwrap())
+            .initial_genomes(initial_genomes)
+            .population_size(5)
+            .max_generations(0)
+            .seed(11)
+            .scorer(FnScorer(count_ones))
+            .selector(Tournament::of_size::<2>())
+            .variation(Variation::mutation(WithOneOverLength))
+            .inspector(on_generation(|_, population: &Population<_, _>| {
+                initial_population.clone_from(population);
+            }))
+            .build()
+            .execute()?;
+        Ok(initial_population)
+    }
+
+    #[test]
+    fn initial_genomes_are_topped_up_with_random_genomes() {
+        let all_ones = Bitstring::from_iter([true; 8]);
+        let all_zeros = Bitstring::from_iter([false; 8]);
+        let population = seeded_run(vec![all_ones.clone(), all_zeros.clone()]).unwrap();
+
+        assert_eq!(population.len(), 5);
+        assert_eq!(population[0].genome, all_ones);
+        assert_eq!(population[0].test_results, Score(8));
+        assert_eq!(population[1].genome, all_zeros);
+        assert!(population[2..]
+            .iter()
+            .all(|individual| individual.genome.bits.len() == 8));
+
+        // Without any initial genomes, the whole population is random.
+        assert_eq!(seeded_run(Vec::new()).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn too_many_initial_genomes() {
+        let genomes = vec![Bitstring::from_iter([true; 8]); 6];
+        let error = seeded_run(genomes).unwrap_err();
+        assert!(error.to_string().contains("6 initial genomes"));
+    }
+
+    /// Nudges one randomly chosen element of a `Vec<i32>` genome up or down.
+    struct NudgeOne;
+
+    impl Mutator<Vec<i32>> for NudgeOne {
+        type Error = std::convert::Infallible;
+
+        fn mutate<R: rand::Rng + ?Sized>(
+            &self,
+            mut genome: Vec<i32>,
+            rng: &mut R,
+        ) -> Result<Vec<i32>, Self::Error> {
+            let index = rng.random_range(0..genome.len());
+            genome[index] += if rng.random() { 1 } else { -1 };
+            Ok(genome)
+        }
+    }
+
+    #[test]
+    fn run_evolves_non_bitstring_genomes() {
+        let total_error =
+            |genome: &Vec<i32>| Error(genome.iter().map(|x| x.unsigned_abs()).sum::<u32>());
+        let report = Run::builder()
+            .genome_maker(Uniform::new(-20, 20).unwrap().into_collection_generator(5))
+            .population_size(50)
+            .num_elites(1)
+            .max_generations(30)
+            .seed(5)
+            .scorer(FnScorer(total_error))
+            .selector(Tournament::of_size::<2>())
+            .variation(Variation::mutation(NudgeOne))
+            .inspector(())
+            .build()
+            .execute()
+            .unwrap();
+
+        let best = report.search.best.unwrap();
+        assert!(
+            best.step > 0,
+            "The run never improved on the initial population"
+        );
+        assert_eq!(best.score, total_error(&best.genome));
+        assert_eq!(best.genome.len(), 5);
+    }
+
+    #[test]
+    fn resumed_run_matches_uninterrupted_run() {
+        let path = std::env::temp_dir().join(format!(
+            "course-helpers-resume-test-{}.txt",
+            std::process::id()
+        ));
+        let run = |max_generations, checkpointing, resume_from| {
+            Run::builder()
+                .genome_maker(random_bitstrings(32, 0.5).unwrap())
+                .population_size(20)
+                .max_generations(max_generations)
+                .seed(99)
+                .maybe_checkpointing(checkpointing)
+                .maybe_resume_from(resume_from)
+                .scorer(FnScorer(count_ones))
+                .selector(Tournament::of_size::<2>())
+                .variation(Variation::mutation(WithOneOverLength))
+                .inspector(())
+                .build()
+                .execute()
+                .unwrap()
+        };
+
+        let uninterrupted = run(10, None, None);
+        // This saves its last checkpoint at generation 5.
+        let _ = run(7, Some(Checkpointing::new(&path, 5, BitstringCodec)), None);
+        let checkpoint = Checkpoint::load(&path, &BitstringCodec).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(checkpoint.generation, 5);
+        let resumed = run(10, None, Some(checkpoint));
+
+        assert_eq!(resumed.final_population, uninterrupted.final_population);
+        assert_eq!(resumed.search.evaluations, uninterrupted.search.evaluations);
+    }
+
+    #[derive(Default)]
+    struct CountStarts(usize);
+
+    impl<G, Sc> Inspector<G, Sc> for CountStarts {
+        fn on_start(&mut self) {
+            self.0 += 1;
+        }
+    }
+
+    #[test]
+    fn resuming_checks_the_checkpoint() {
+        let checkpoint = Checkpoint {
+            seed: 99,
+            generation: 5,
+            evaluations: 60,
+            best: None,
+            population: vec![Bitstring::from_iter([true; 32]); 10],
+        };
+        let mut starts = CountStarts::default();
+        let mut resume = |population_size, seed| {
+            Run::builder()
+                .genome_maker(random_bitstrings(32, 0.5).unwrap())
+                .population_size(population_size)
+                .max_generations(10)
+                .maybe_seed(seed)
+                .resume_from(checkpoint.clone())
+                .scorer(FnScorer(count_ones))
+                .selector(Tournament::of_size::<2>())
+                .variation(Variation::mutation(WithOneOverLength))
+                .inspector(&mut starts)
+                .build()
+                .execute()
+        };
+
+        assert!(resume(10, None).is_ok());
+        assert!(resume(10, Some(99)).is_ok());
+        let error = resume(20, None).unwrap_err();
+        assert!(error.to_string().contains("population size"));
+        let error = resume(10, Some(1)).unwrap_err();
+        assert!(error.to_string().contains("seed"));
+        // Only the two runs that actually started should have been inspected.
+        assert_eq!(starts.0, 2);
+    }
+
+    #[test]
+    fn zero_checkpoint_interval_is_rejected() {
+        let error = Run::builder()
+            .genome_maker(random_bitstrings(32, 0.5).unwrap())
+            .population_size(10)
+            .max_generations(10)
+            .checkpointing(Checkpointing::new("unused.txt", 0, BitstringCodec))
+            .scorer(FnScorer(count_ones))
+            .selector(Tournament::of_size::<2>())
+            .variation(Variation::mutation(WithOneOverLength))
+            .inspector(())
+            .build()
+            .execute()
+            .unwrap_err();
+        assert!(error.to_string().contains("checkpoint interval"));
+    }
+
+    /// Removes the first gene, so the shortest `Plushy`s (the best ones with
+    /// the scorer in `resumed_plushy_run`) keep getting shorter.
+    struct DropFirstGene;
+
+    impl Mutator<Plushy> for DropFirstGene {
+        type Error = std::convert::Infallible;
+
+        fn mutate<R: rand::Rng + ?Sized>(
+            &self,
+            genome: Plushy,
+            _: &mut R,
+        ) -> Result<Plushy, Self::Error> {
+            Ok(Plushy::new(
+                genome.get_genes().into_iter().skip(1).collect(),
+            ))
+        }
+    }
+
+    #[test]
+    fn resumed_plushy_run() {
+        let instructions: Vec<PushInstruction> = vec![
+            IntInstruction::Add.into(),
+            IntInstruction::Multiply.into(),
+            FloatInstruction::Add.into(),
+        ];
+        let codec = PlushyCodec::new(instructions.clone());
+        let genes = instructions
+            .into_iter()
+            .map(PushGene::Instruction)
+            .chain([PushGene::Close])
+            .cycle();
+        let population = (1..=10)
+            .map(|length| Plushy::new(genes.clone().take(length).collect()))
+            .collect();
+        let path = std::env::temp_dir().join(format!(
+            "course-helpers-plushy-resume-test-{}.txt",
+            std::process::id()
+        ));
+        Checkpoint {
+            seed: 5,
+            generation: 4,
+            evaluations: 50,
+            best: None,
+            population,
+        }
+        .save(&path, &codec)
+        .unwrap();
+
+        let report = Run::builder()
+            .genome_maker(NoGenes)
+            .population_size(10)
+            .max_generations(8)
+            .checkpointing(Checkpointing::new(&path, 2, codec.clone()))
+            .resume_from(Checkpoint::load(&path, &codec).unwrap())
+            .scorer(FnScorer(|plushy: &Plushy| Error(plushy.get_genes().len())))
+            .selector(Tournament::of_size::<2>())
+            .variation(Variation::mutation(DropFirstGene))
+            .inspector(())
+            .build()
+            .execute()
+            .unwrap();
+        let checkpoint = Checkpoint::load(&path, &codec).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(report.search.steps, 8);
+        assert_eq!(report.search.evaluations, 50 + 4 * 10);
+        assert_eq!(checkpoint.generation, 8);
+        assert_eq!(
+            checkpoint.population,
+            report
+                .final_population
+                .iter()
+                .map(|individual| individual.genome.clone())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    /// The genome maker isn't used when a run is resumed.
+    struct NoGenes;
+
+    impl Distribution<Plushy> for NoGenes {
+        fn sample<R: rand::Rng + ?Sized>(&self, _: &mut R) -> Plushy {
+            Plushy::new(Vec::new())
+        }
+    }
+
+    #[test]
+    fn steady_state_inspects_every_population_size_births() {
+        let steady_state = |parallel_evaluation| {
+            let mut inspected = Vec::new();
+            let report = Run::builder()
+                .genome_maker(random_bitstrings(32, 0.5).unwrap())
+                .population_size(20)
+                .num_elites(1)
+                .max_generations(5)
+                .parallel_evaluation(parallel_evaluation)
+                .replacement(Replacement::SteadyState {
+                    children_per_step: 3,
+                    victim: Victim::ReverseTournament(2),
+                })
+                .seed(23)
+                .scorer(FnScorer(count_ones))
+                .selector(Tournament::of_size::<2>())
+                .variation(Variation::mutation(WithOneOverLength))
+                .inspector(on_generation(|generation, _| inspected.push(generation)))
+                .build()
+                .execute()
+                .unwrap();
+            assert_eq!(inspected, [0, 1, 2, 3, 4, 5]);
+            report
+        };
+
+        let serial = steady_state(false);
+        assert_eq!(serial.search.evaluations, 20 * 6);
+        assert_eq!(serial.final_population, steady_state(true).final_population);
+    }
+}
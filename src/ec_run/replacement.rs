@@ -0,0 +1,128 @@
+use ec_core::individual::ec::EcIndividual;
+use rand::{seq::IndexedRandom, Rng};
+
+/// How a [`Run`](super::Run) replaces individuals in the population.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Replacement {
+    /// Replace the whole population (apart from any elites) every generation.
+    #[default]
+    Generational,
+    /// Make `children_per_step` children at a time, each of which immediately
+    /// replaces an individual chosen by `victim`, so later children can have
+    /// parents born earlier in the same "generation".
+    ///
+    /// A generation in steady-state mode is "population-size" births, so the
+    /// inspector, termination criteria, and checkpoints see the population
+    /// at the same number of evaluations as they would in a generational run.
+    /// The best `num_elites` individuals are never replaced.
+    SteadyState {
+        children_per_step: usize,
+        victim: Victim,
+    },
+}
+
+/// Which individual a new child replaces in steady-state mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Victim {
+    /// The worst individual in the population.
+    Worst,
+    /// A random individual.
+    Random,
+    /// The loser of a tournament of this size, i.e., the worst of this many
+    /// randomly chosen individuals.
+    ReverseTournament(usize),
+}
+
+/// Errors that can occur while replacing individuals in steady-state mode.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum ReplacementError {
+    #[error("Every individual is protected, so there's nothing to replace")]
+    NothingToReplace,
+}
+
+/// The positions in `population` of every individual except the
+/// `num_protected` best ones, in population order.
+///
+/// This sorts the population, so [`Run`](super::Run) does it once per step
+/// rather than once per child. Ties are broken by position like `elites()`.
+pub(crate) fn unprotected<G, Sc: Ord>(
+    population: &[EcIndividual<G, Sc>],
+    num_protected: usize,
+) -> Vec<usize> {
+    let mut candidates = (0..population.len()).collect::<Vec<_>>();
+    if num_protected > 0 {
+        // Best first, and then we drop the protected ones off the front.
+        candidates.sort_by(|&first, &second| {
+            population[second]
+                .test_results
+                .cmp(&population[first].test_results)
+        });
+        candidates.drain(..num_protected.min(population.len()));
+        candidates.sort_unstable();
+    }
+    candidates
+}
+
+impl Victim {
+    /// The position in `population` of the individual to replace, chosen from
+    /// the `candidates` (see [`unprotected`]).
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if there are no candidates.
+    pub(crate) fn choose<G, Sc, R>(
+        self,
+        population: &[EcIndividual<G, Sc>],
+        candidates: &[usize],
+        rng: &mut R,
+    ) -> Result<usize, ReplacementError>
+    where
+        Sc: Ord,
+        R: Rng + ?Sized,
+    {
+        let score = |index: &usize| &population[*index].test_results;
+        match self {
+            // `min_by_key` returns the first of equally bad individuals, which
+            // keeps seeded runs reproducible.
+            Self::Worst => candidates.iter().copied().min_by_key(score),
+            Self::Random => candidates.choose(rng).copied(),
+            Self::ReverseTournament(size) => (0..size.max(1))
+                .filter_map(|_| candidates.choose(rng).copied())
+                .min_by_key(score),
+        }
+        .ok_or(ReplacementError::NothingToReplace)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn victims_are_never_protected() {
+        let population = [3, 1, 4, 1, 5, 9, 2, 6]
+            .into_iter()
+            .map(|score| EcIndividual::new((), score))
+            .collect::<Vec<_>>();
+        let mut rng = StdRng::seed_from_u64(0);
+
+        let choose = |victim: Victim, num_protected, rng: &mut StdRng| {
+            victim.choose(&population, &unprotected(&population, num_protected), rng)
+        };
+        assert_eq!(choose(Victim::Worst, 0, &mut rng), Ok(1));
+        // Of the two 1s, the first one is ranked higher, so it's protected.
+        assert_eq!(choose(Victim::Worst, 7, &mut rng), Ok(3));
+        for _ in 0..100 {
+            let victim = choose(Victim::Random, 3, &mut rng).unwrap();
+            assert!(population[victim].test_results < 5);
+            let victim = choose(Victim::ReverseTournament(2), 3, &mut rng).unwrap();
+            assert!(population[victim].test_results < 5);
+        }
+        assert_eq!(
+            choose(Victim::Random, 8, &mut rng),
+            Err(ReplacementError::NothingToReplace)
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use std::fmt::Debug;
+
+use ec_core::{
+    individual::scorer::Scorer as IndividualScorer,
+    operator::{mutator::Mutator, recombinator::Recombinator, selector::Selector},
+};
+use rand::distr::Distribution;
+
+use super::{Population, Replacement, Run, RunReport};
+use crate::{
+    inspector::Inspector,
+    report::BestFound,
+    seeding::derive_seed,
+    termination::{ProgressTracker, TerminationReason},
+};
+
+/// A [`Run`] that the caller runs one generation at a time, e.g., to look at
+/// the population between generations, change course partway through an
+/// experiment, or drive the run from a test or a REPL. Make one with
+/// [`Run::stepper`].
+///
+/// The inspector sees exactly what it would in [`Run::execute`], and stepping
+/// until [`RunStepper::step`] returns `false` gives the same result as
+/// `execute`.
+pub struct RunStepper<G, GM, Scorer, Sel, Rec, Mut, Ins>
+where
+    G: Clone + Send + Sync,
+    GM: Distribution<G>,
+    Scorer: IndividualScorer<G> + Send + Sync,
+    Scorer::Score: Debug + Clone + Send + Sync + Ord,
+    Sel: Selector<Population<G, Scorer::Score>> + Send + Sync,
+    Rec: Recombinator<[G; 2], Output = G> + Send + Sync,
+    Mut: Mutator<G> + Send + Sync,
+    Sel::Error: std::error::Error + Send + Sync + 'static,
+    Rec::Error: std::error::Error + Send + Sync + 'static,
+    Mut::Error: std::error::Error + Send + Sync + 'static,
+    Ins: Inspector<G, Scorer::Score>,
+{
+    pub(super) run: Run<G, GM, Scorer, Sel, Rec, Mut, Ins>,
+    pub(super) tracker: ProgressTracker<G, Scorer::Score>,
+    pub(super) population: Population<G, Scorer::Score>,
+    pub(super) termination_reason: Option<TerminationReason>,
+}
+
+impl<G, GM, Scorer, Sel, Rec, Mut, Ins> RunStepper<G, GM, Scorer, Sel, Rec, Mut, Ins>
+where
+    G: Clone + Send + Sync,
+    GM: Distribution<G>,
+    Scorer: IndividualScorer<G> + Send + Sync,
+    Scorer::Score: Debug + Clone + Send + Sync + Ord,
+    Sel: Selector<Population<G, Scorer::Score>> + Send + Sync,
+    Rec: Recombinator<[G; 2], Output = G> + Send + Sync,
+    Mut: Mutator<G> + Send + Sync,
+    Sel::Error: std::error::Error + Send + Sync + 'static,
+    Rec::Error: std::error::Error + Send + Sync + 'static,
+    Mut::Error: std::error::Error + Send + Sync + 'static,
+    Ins: Inspector<G, Scorer::Score>,
+{
+    /// Make the next generation (and save a checkpoint, if it's time to).
+    /// Returns `false`, without doing anything, if the run is over.
+    ///
+    /// # Errors
+    ///
+    /// This can return an error if selection fails because the population is
+    /// empty (this should never happen), if creating the new generation fails,
+    /// or if saving a checkpoint fails.
+    pub fn step(&mut self) -> anyhow::Result<bool> {
+        if self.termination_reason.is_none() {
+            self.termination_reason = self.check();
+        }
+        if self.termination_reason.is_some() {
+            return Ok(false);
+        }
+
+        let run = &mut self.run;
+        let tracker = &mut self.tracker;
+        tracker.finish_step();
+        let generation_seed = derive_seed(tracker.seed(), tracker.steps() as u64);
+        match run.replacement {
+            Replacement::Generational => {
+                self.population = run.next_generation(&self.population, generation_seed)?;
+                // The elites at the front of the population weren't re-evaluated,
+                // so we only record the new children.
+                run.record_scores(tracker, &self.population[run.num_elites..]);
+            }
+            Replacement::SteadyState {
+                children_per_step,
+                victim,
+            } => {
+                let births = run.steady_state_generation(
+                    &mut self.population,
+                    generation_seed,
+                    children_per_step,
+                    victim,
+                )?;
+                run.record_scores(tracker, &births);
+            }
+        }
+        run.save_checkpoint(tracker, &self.population)?;
+        run.inspector
+            .on_generation(tracker.steps(), &self.population);
+        Ok(true)
+    }
+
+    /// Why the run would stop now, if it would.
+    fn check(&self) -> Option<TerminationReason> {
+        self.tracker.check(&self.run.termination).or_else(|| {
+            (self.tracker.steps() >= self.run.max_generations)
+                .then_some(TerminationReason::MaxSteps)
+        })
+    }
+
+    /// The current population.
+    pub const fn population(&self) -> &Population<G, Scorer::Score> {
+        &self.population
+    }
+
+    /// The number of the current generation, where the initial population is
+    /// generation 0.
+    pub const fn generation(&self) -> usize {
+        self.tracker.steps()
+    }
+
+    /// The best genome found so far.
+    pub const fn best(&self) -> Option<&BestFound<G, Scorer::Score>> {
+        self.tracker.best()
+    }
+
+    /// The number of genomes evaluated so far.
+    pub const fn evaluations(&self) -> usize {
+        self.tracker.evaluations()
+    }
+
+    /// Why the run stopped, once [`RunStepper::step`] has returned `false`.
+    pub const fn termination_reason(&self) -> Option<&TerminationReason> {
+        self.termination_reason.as_ref()
+    }
+
+    /// End the run, returning the same report as [`Run::execute`]. If the run
+    /// would have kept going, the termination reason is
+    /// [`TerminationReason::Stopped`].
+    pub fn finish(mut self) -> RunReport<G, Scorer::Score> {
+        let reason = self
+            .termination_reason
+            .take()
+            .or_else(|| self.check())
+            .unwrap_or(TerminationReason::Stopped);
+        let search = self.tracker.into_report(reason);
+        self.run.inspector.on_finish(&search);
+        RunReport {
+            search,
+            final_population: self.population,
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use std::{convert::Infallible, ops::Range};
+
+use ec_core::{
+    individual::scorer::Scorer,
+    operator::{
+        genome_extractor::GenomeExtractor,
+        genome_scorer::GenomeScorer,
+        mutator::{Mutate, Mutator},
+        recombinator::{Recombinator, Recombine},
+        selector::{Select, Selector},
+        Composable, Operator,
+    },
+};
+use rand::Rng;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use super::Population;
+use crate::seeding::stream_rng;
+
+/// The variation operators a [`Run`](super::Run) uses to turn selected parents
+/// into a new child.
+///
+/// A run has to use at least one of recombination and mutation, but it doesn't
+/// have to use both. Use [`Variation::recombination`], [`Variation::mutation`],
+/// or [`Variation::recombination_and_mutation`] to construct one; the first two
+/// fill in the operator that isn't used with [`NoRecombination`] or
+/// [`NoMutation`] so that Rust can infer all the types.
+#[derive(Debug, Clone)]
+pub enum Variation<Rec, Mut> {
+    /// Select two parents and recombine their genomes into a child genome.
+    Recombination(Rec),
+    /// Select one parent and mutate (a copy of) its genome.
+    Mutation(Mut),
+    /// Select two parents, recombine their genomes, and then mutate the result.
+    RecombinationAndMutation(Rec, Mut),
+}
+
+impl<Rec> Variation<Rec, NoMutation> {
+    /// Crossover-only variation.
+    pub const fn recombination(recombinator: Rec) -> Self {
+        Self::Recombination(recombinator)
+    }
+}
+
+impl<Mut> Variation<NoRecombination, Mut> {
+    /// Mutation-only variation, e.g., `Umad` in PushGP.
+    pub const fn mutation(mutator: Mut) -> Self {
+        Self::Mutation(mutator)
+    }
+}
+
+impl<Rec, Mut> Variation<Rec, Mut> {
+    /// Recombine two parents and then mutate the resulting child.
+    pub const fn recombination_and_mutation(recombinator: Rec, mutator: Mut) -> Self {
+        Self::RecombinationAndMutation(recombinator, mutator)
+    }
+
+    /// Make the children numbered `child_numbers` from `population`, in
+    /// parallel if `parallel` is set. Each child gets its own RNG, derived
+    /// from `seed` and the child's number, so serial and parallel evaluation
+    /// make exactly the same children.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if selection, recombination, or mutation fails.
+    pub(crate) fn make_children<G, Sc, Sel, Scr>(
+        &self,
+        selector: &Sel,
+        scorer: &Scr,
+        population: &Population<G, Sc>,
+        seed: u64,
+        child_numbers: Range<usize>,
+        parallel: bool,
+    ) -> anyhow::Result<Population<G, Sc>>
+    where
+        G: Clone + Send + Sync,
+        Sc: Send + Sync,
+        Sel: Selector<Population<G, Sc>> + Sync,
+        Sel::Error: std::error::Error + Send + Sync + 'static,
+        Rec: Recombinator<[G; 2], Output = G> + Sync,
+        Rec::Error: std::error::Error + Send + Sync + 'static,
+        Mut: Mutator<G> + Sync,
+        Mut::Error: std::error::Error + Send + Sync + 'static,
+        Scr: Scorer<G, Score = Sc> + Sync,
+    {
+        match self {
+            Self::Recombination(recombinator) => {
+                let child_maker =
+                    // Select two parents
+                    Select::new(selector)
+                    .apply_twice()
+                    // Extract the genomes from those two parents, yielding a pair of genomes
+                    .then_map(GenomeExtractor)
+                    // Combine those genomes into a new child genome
+                    .then(Recombine::new(recombinator))
+                    // Score the child genome to generate an `Individual`
+                    .wrap::<GenomeScorer<_, _>>(scorer);
+                apply_child_maker(&child_maker, population, seed, child_numbers, parallel)
+            }
+            Self::Mutation(mutator) => {
+                let child_maker =
+                    // Select one parent and extract its genome
+                    Select::new(selector)
+                    .then(GenomeExtractor)
+                    // Mutate (a copy of) the parent's genome
+                    .then(Mutate::new(mutator))
+                    // Score the child genome to generate an `Individual`
+                    .wrap::<GenomeScorer<_, _>>(scorer);
+                apply_child_maker(&child_maker, population, seed, child_numbers, parallel)
+            }
+            Self::RecombinationAndMutation(recombinator, mutator) => {
+                let child_maker =
+                    // Select two parents
+                    Select::new(selector)
+                    .apply_twice()
+                    // Extract the genomes from those two parents, yielding a pair of genomes
+                    .then_map(GenomeExtractor)
+                    // Combine those genomes into a new child genome
+                    .then(Recombine::new(recombinator))
+                    // Mutate the resulting genome
+                    .then(Mutate::new(mutator))
+                    // Score the child genome to generate an `Individual`
+                    .wrap::<GenomeScorer<_, _>>(scorer);
+                apply_child_maker(&child_maker, population, seed, child_numbers, parallel)
+            }
+        }
+    }
+}
+
+/// Apply `child_maker` to `population` once for each of the `child_numbers`,
+/// using the RNG for that child's number.
+///
+/// We don't use `ec_core`'s `Generation` to do this because its `par_next` and
+/// `serial_next` use thread-local RNGs, which would make seeded runs
+/// unrepeatable, and because it always replaces the whole population, which
+/// doesn't leave room for elites or steady-state replacement.
+#[expect(clippy::match_bool, reason = "I like the `match` instead of `if`")]
+fn apply_child_maker<'pop, P, C>(
+    child_maker: &C,
+    population: &'pop P,
+    seed: u64,
+    child_numbers: Range<usize>,
+    parallel: bool,
+) -> anyhow::Result<Vec<C::Output>>
+where
+    P: Sync,
+    C: Operator<&'pop P> + Sync,
+    C::Output: Send,
+    C::Error: std::error::Error + Send + Sync + 'static,
+{
+    let make_child = |child_number| -> anyhow::Result<C::Output> {
+        Ok(child_maker.apply(population, &mut stream_rng(seed, child_number))?)
+    };
+    match parallel {
+        true => child_numbers.into_par_iter().map(make_child).collect(),
+        false => child_numbers.map(make_child).collect(),
+    }
+}
+
+/// Stands in for the recombinator in a mutation-only [`Variation`].
+///
+/// This is never actually applied; if it were, it would just return the
+/// first parent unchanged.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoRecombination;
+
+impl<G> Recombinator<[G; 2]> for NoRecombination {
+    type Output = G;
+    type Error = Infallible;
+
+    fn recombine<R: Rng + ?Sized>(
+        &self,
+        [first_parent, _]: [G; 2],
+        _: &mut R,
+    ) -> Result<Self::Output, Self::Error> {
+        Ok(first_parent)
+    }
+}
+
+/// Stands in for the mutator in a recombination-only [`Variation`].
+///
+/// This is never actually applied; if it were, it would just return the
+/// genome unchanged.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoMutation;
+
+impl<G> Mutator<G> for NoMutation {
+    type Error = Infallible;
+
+    fn mutate<R: Rng + ?Sized>(&self, genome: G, _: &mut R) -> Result<G, Self::Error> {
+        Ok(genome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use ec_core::distributions::collection::ConvertToCollectionGenerator;
+    use ec_core::{
+        individual::scorer::FnScorer, operator::selector::tournament::Tournament,
+        test_results::Error,
+    };
+    use rand::distr::Uniform;
+
+    use super::*;
+    use crate::{
+        ec_run::Run,
+        inspector::on_generation,
+        termination::{Termination, TerminationReason},
+        test_support::Step,
+    };
+
+    /// Takes each element of the child from one of the two parents at random.
+    struct UniformXo;
+
+    impl Recombinator<[Vec<i32>; 2]> for UniformXo {
+        type Output = Vec<i32>;
+        type Error = Infallible;
+
+        fn recombine<R: Rng + ?Sized>(
+            &self,
+            [first, second]: [Vec<i32>; 2],
+            rng: &mut R,
+        ) -> Result<Vec<i32>, Self::Error> {
+            Ok(first
+                .into_iter()
+                .zip(second)
+                .map(|(x, y)| if rng.random() { x } else { y })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn mutation_only_run() {
+        let report = Run::builder()
+            .genome_maker(Uniform::new(0, 100).unwrap())
+            .population_size(20)
+            .max_generations(100)
+            .termination(Termination::TargetScore(Error(0)))
+            .seed(3)
+            .scorer(FnScorer(|genome: &i32| Error(genome.abs_diff(42))))
+            .selector(Tournament::of_size::<2>())
+            .variation(Variation::mutation(Step))
+            .inspector(())
+            .build()
+            .execute()
+            .unwrap();
+        assert_eq!(
+            report.search.termination_reason,
+            TerminationReason::TargetScore
+        );
+        assert_eq!(report.search.best.unwrap().genome, 42);
+    }
+
+    #[test]
+    fn crossover_only_run() {
+        let total_error =
+            |genome: &Vec<i32>| Error(genome.iter().map(|x| x.unsigned_abs()).sum::<u32>());
+        let mut mean_errors = Vec::new();
+        let report = Run::builder()
+            .genome_maker(
+                Uniform::new_inclusive(-3, 3)
+                    .unwrap()
+                    .into_collection_generator(6),
+            )
+            .population_size(50)
+            .max_generations(20)
+            .seed(3)
+            .scorer(FnScorer(total_error))
+            .selector(Tournament::of_size::<2>())
+            .variation(Variation::recombination(UniformXo))
+            .inspector(on_generation(
+                |_, population: &Population<Vec<i32>, Error<u32>>| {
+                    let total: u32 = population.iter().map(|ind| ind.test_results.0).sum();
+                    mean_errors.push(f64::from(total) / population.len() as f64);
+                },
+            ))
+            .build()
+            .execute()
+            .unwrap();
+        assert!(mean_errors.last() < mean_errors.first());
+        // Without mutation, every gene comes from the initial population.
+        assert!(report
+            .final_population
+            .iter()
+            .flat_map(|individual| &individual.genome)
+            .all(|gene| (-3..=3).contains(gene)));
+    }
+}
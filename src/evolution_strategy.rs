@@ -0,0 +1,473 @@
+use core::slice;
+use std::{cmp::Ordering, convert::Infallible, marker::PhantomData};
+
+use bon::Builder;
+use ec_core::{individual::scorer::Scorer, operator::mutator::Mutator};
+use ec_linear::genome::bitstring::Bitstring;
+use rand::{prelude::Distribution, Rng};
+
+use crate::{
+    hill_climber::{finish_search, sample_neighbors, start_search, Solution},
+    inspector::{Inspector, SearchEvent},
+    report::SearchReport,
+    termination::Termination,
+};
+
+/// A mutator whose strength is controlled by a step size (e.g., a mutation
+/// rate, or the size of a change to a number), so an [`EvolutionStrategy`] can
+/// adapt it as the search goes on.
+pub trait StepSizeMutator<G> {
+    type Error;
+
+    /// Mutate `genome`, with bigger changes for bigger step sizes.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if the mutation fails.
+    fn mutate_with_step_size<R: Rng + ?Sized>(
+        &self,
+        genome: G,
+        step_size: f64,
+        rng: &mut R,
+    ) -> Result<G, Self::Error>;
+}
+
+/// Flip each bit of a `Bitstring` independently, where the step size is the
+/// _expected_ number of bits flipped. A step size of 1 is the same as the
+/// usual "one over length" mutation rate.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FlipBits;
+
+impl StepSizeMutator<Bitstring> for FlipBits {
+    type Error = Infallible;
+
+    fn mutate_with_step_size<R: Rng + ?Sized>(
+        &self,
+        mut genome: Bitstring,
+        step_size: f64,
+        rng: &mut R,
+    ) -> Result<Bitstring, Self::Error> {
+        if genome.bits.is_empty() {
+            return Ok(genome);
+        }
+        #[expect(
+            clippy::cast_precision_loss,
+            reason = "Genomes are far shorter than 2^52 bits."
+        )]
+        let mutation_rate = (step_size / genome.bits.len() as f64).clamp(0.0, 1.0);
+        for bit in &mut genome.bits {
+            if rng.random_bool(mutation_rate) {
+                *bit = !*bit;
+            }
+        }
+        Ok(genome)
+    }
+}
+
+/// A [`StepSizeMutator`] with a fixed step size, so we can use it wherever a
+/// plain `Mutator` is needed.
+struct WithStepSize<'a, M> {
+    mutator: &'a M,
+    step_size: f64,
+}
+
+impl<G, M> Mutator<G> for WithStepSize<'_, M>
+where
+    M: StepSizeMutator<G>,
+{
+    type Error = M::Error;
+
+    fn mutate<R: Rng + ?Sized>(&self, genome: G, rng: &mut R) -> Result<G, Self::Error> {
+        self.mutator
+            .mutate_with_step_size(genome, self.step_size, rng)
+    }
+}
+
+/// Which solutions an [`EvolutionStrategy`] picks the next parent from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EsSelection {
+    /// (1+λ): the best of the parent _and_ its children, so the parent is only
+    /// replaced by a child that's at least as good.
+    #[default]
+    Plus,
+    /// (1,λ): the best of the children, even if it's worse than the parent.
+    Comma,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum EvolutionStrategyError<MutationError> {
+    #[error(transparent)]
+    Mutation(#[from] MutationError),
+
+    #[error("An evolution strategy needs at least one child per step")]
+    NoChildren,
+
+    #[error("The step size bounds must be numbers with min ({min}) <= max ({max})")]
+    InvalidStepSizeBounds { min: f64, max: f64 },
+
+    #[error("The initial step size ({0}) must be a finite number")]
+    InvalidInitialStepSize(f64),
+
+    #[error("The adaptation factor ({0}) must be a finite number bigger than zero")]
+    InvalidAdaptationFactor(f64),
+}
+
+#[derive(Debug, Builder)]
+pub struct EvolutionStrategy<Ge, GM, Mut, Sc, Scr, Ins>
+// You typically wouldn't put all these constraints on the struct itself, instead
+// you'd put them on the `impl` block for the struct. But I'm doing it here to
+// make the constraints more visible and (hopefully) make some of the error
+// messages more helpful for people new to Rust.
+where
+    Ge: Clone + Send + Sync,
+    GM: Distribution<Ge>,
+    Mut: StepSizeMutator<Ge> + Sync,
+    Mut::Error: Send,
+    Sc: Ord + PartialOrd + Send,
+    Scr: Scorer<Ge, Score = Sc> + Sync,
+    // The inspector is shown each new parent as a
+    // (sample number, genome, score) triple, and the step size used in each
+    // step.
+    Ins: Inspector<Ge, Sc>,
+{
+    // See `HillClimber` for why we need this.
+    #[builder(field)]
+    _p: PhantomData<Ge>,
+
+    #[builder(default = 1_000)]
+    num_to_search: usize,
+
+    /// The number of children (λ) made from the parent in each step.
+    #[builder(default = 10)]
+    num_children_per_step: usize,
+
+    #[builder(default)]
+    selection: EsSelection,
+
+    #[builder(default = 1.0)]
+    initial_step_size: f64,
+
+    /// The 1/5th success rule: after each step, the step size is multiplied by
+    /// this if more than a fifth of the children were better than their
+    /// parent, and divided by it if fewer were.
+    #[builder(default = 1.22)]
+    adaptation_factor: f64,
+
+    #[builder(default = 0.0)]
+    min_step_size: f64,
+
+    #[builder(default = f64::INFINITY)]
+    max_step_size: f64,
+
+    /// Mutate and score the children in each step in parallel. The serial and
+    /// parallel searches make the same children, and pick the same parents.
+    #[builder(default = false)]
+    parallel_search: bool,
+
+    /// Additional criteria for stopping the search before `num_to_search`
+    /// genomes have been evaluated.
+    #[builder(default)]
+    termination: Termination<Sc>,
+
+    /// The seed for the random number generator. If this isn't specified a
+    /// random seed is used, which is included in the returned report.
+    seed: Option<u64>,
+
+    genome_maker: GM,
+    mutator: Mut,
+    scorer: Scr,
+    inspector: Ins,
+}
+
+impl<Ge, GM, Mut, Sc, Scr, Ins> EvolutionStrategy<Ge, GM, Mut, Sc, Scr, Ins>
+where
+    Ge: Clone + Send + Sync,
+    GM: Distribution<Ge>,
+    Mut: StepSizeMutator<Ge> + Sync,
+    Mut::Error: Send,
+    Sc: Ord + PartialOrd + Clone + Send,
+    Scr: Scorer<Ge, Score = Sc> + Sync,
+    Ins: Inspector<Ge, Sc>,
+{
+    /// Evolve from a randomly generated parent, returning a report with the
+    /// best genome found and the reason the search stopped.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if mutating a genome fails, if
+    /// `num_children_per_step` is zero, if `min_step_size` is bigger than
+    /// `max_step_size` (or either is NaN), if `initial_step_size` isn't
+    /// finite, or if `adaptation_factor` isn't finite and positive.
+    pub fn search(&mut self) -> Result<SearchReport<Ge, Sc>, EvolutionStrategyError<Mut::Error>> {
+        if self.num_children_per_step == 0 {
+            return Err(EvolutionStrategyError::NoChildren);
+        }
+        if self.min_step_size.is_nan()
+            || self.max_step_size.is_nan()
+            || self.min_step_size > self.max_step_size
+        {
+            return Err(EvolutionStrategyError::InvalidStepSizeBounds {
+                min: self.min_step_size,
+                max: self.max_step_size,
+            });
+        }
+        if !self.initial_step_size.is_finite() {
+            return Err(EvolutionStrategyError::InvalidInitialStepSize(
+                self.initial_step_size,
+            ));
+        }
+        if !self.adaptation_factor.is_finite() || self.adaptation_factor <= 0.0 {
+            return Err(EvolutionStrategyError::InvalidAdaptationFactor(
+                self.adaptation_factor,
+            ));
+        }
+        let (mut tracker, mut parent) = start_search(
+            self.seed,
+            &self.genome_maker,
+            &self.scorer,
+            &mut self.inspector,
+        );
+        let seed = tracker.seed();
+
+        let mut step_size = self
+            .initial_step_size
+            .clamp(self.min_step_size, self.max_step_size);
+        let mut reason = None;
+        for chunk_start in (1..self.num_to_search).step_by(self.num_children_per_step) {
+            reason = tracker.check(&self.termination);
+            if reason.is_some() {
+                break;
+            }
+            tracker.finish_step();
+            self.inspector.on_event(&SearchEvent::StepSize {
+                step: tracker.steps(),
+                step_size,
+            });
+
+            let chunk_end = self
+                .num_to_search
+                .min(chunk_start + self.num_children_per_step);
+            let mutator = WithStepSize {
+                mutator: &self.mutator,
+                step_size,
+            };
+            let children = sample_neighbors(
+                &mutator,
+                &self.scorer,
+                &parent.1,
+                seed,
+                chunk_start..chunk_end,
+                self.parallel_search,
+            )?;
+            let num_children = children.len();
+
+            // Like `HillClimber`, we pick the last of equally good children.
+            let mut num_successes = 0;
+            let mut best_child: Option<Solution<Ge, Sc>> = None;
+            for (sample_number, child, score) in children {
+                tracker.record_and_inspect(sample_number, &child, &score, &mut self.inspector);
+                if score > parent.2 {
+                    num_successes += 1;
+                }
+                if best_child
+                    .as_ref()
+                    .is_none_or(|(_, _, best_score)| score >= *best_score)
+                {
+                    best_child = Some((sample_number, child, score));
+                }
+            }
+            let best_child = best_child.ok_or(EvolutionStrategyError::NoChildren)?;
+
+            step_size = self.adapt(step_size, num_successes, num_children);
+            if self.selection == EsSelection::Comma || best_child.2 >= parent.2 {
+                parent = best_child;
+                self.inspector.on_samples(slice::from_ref(&parent));
+            }
+        }
+
+        Ok(finish_search(
+            tracker,
+            &self.termination,
+            reason,
+            &mut self.inspector,
+        ))
+    }
+
+    /// The step size for the next step, by the 1/5th success rule.
+    fn adapt(&self, step_size: f64, num_successes: usize, num_children: usize) -> f64 {
+        // Compare `num_successes / num_children` to 1/5 without dividing.
+        let adapted = match (5 * num_successes).cmp(&num_children) {
+            Ordering::Greater => step_size * self.adaptation_factor,
+            Ordering::Less => step_size / self.adaptation_factor,
+            Ordering::Equal => step_size,
+        };
+        adapted.clamp(self.min_step_size, self.max_step_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ec_core::{
+        distributions::collection::ConvertToCollectionGenerator, individual::scorer::FnScorer,
+        test_results::Score,
+    };
+    use rand::distr::StandardUniform;
+
+    use super::*;
+    use crate::inspector::on_samples;
+    use crate::test_support::count_ones;
+
+    #[derive(Default)]
+    struct StepSizes(Vec<(usize, f64)>);
+
+    impl<Ge, Sc> Inspector<Ge, Sc> for StepSizes {
+        fn on_event(&mut self, event: &SearchEvent<'_, Ge, Sc>) {
+            if let SearchEvent::StepSize { step, step_size } = event {
+                self.0.push((*step, *step_size));
+            }
+        }
+    }
+
+    /// The scores of the successive parents in a search with a constant,
+    /// large step size, which makes most children near the optimum worse than
+    /// their parent.
+    fn parent_scores(selection: EsSelection) -> (Vec<usize>, Option<Score<usize>>) {
+        let mut scores = Vec::new();
+        let report = EvolutionStrategy::builder()
+            .num_to_search(2_001)
+            .num_children_per_step(5)
+            .selection(selection)
+            .initial_step_size(8.0)
+            .adaptation_factor(1.0)
+            .seed(11)
+            .genome_maker(StandardUniform.into_collection_generator(64))
+            .mutator(FlipBits)
+            .scorer(FnScorer(count_ones))
+            .inspector(on_samples(
+                |solution_chunk: &[(usize, Bitstring, Score<usize>)]| {
+                    scores.extend(solution_chunk.iter().map(|(_, _, score)| score.0));
+                },
+            ))
+            .build()
+            .search()
+            .unwrap();
+        (scores, report.best.map(|best| best.score))
+    }
+
+    #[test]
+    fn comma_strategy_can_replace_the_parent_with_a_worse_child() {
+        let (plus_scores, _) = parent_scores(EsSelection::Plus);
+        assert!(plus_scores.is_sorted());
+
+        let (comma_scores, comma_best) = parent_scores(EsSelection::Comma);
+        assert!(comma_scores.windows(2).any(|pair| pair[1] < pair[0]));
+        // The report still has the best genome seen, not the last parent.
+        assert_eq!(comma_best, comma_scores.iter().copied().max().map(Score));
+    }
+
+    #[test]
+    fn one_fifth_rule() {
+        let strategy = EvolutionStrategy::builder()
+            .adaptation_factor(2.0)
+            .min_step_size(0.25)
+            .max_step_size(8.0)
+            .genome_maker(StandardUniform.into_collection_generator(8))
+            .mutator(FlipBits)
+            .scorer(FnScorer(count_ones))
+            .inspector(())
+            .build();
+        // More than a fifth of the children were better, so grow the step.
+        assert_eq!(strategy.adapt(1.0, 3, 10), 2.0);
+        // Exactly a fifth, so keep it.
+        assert_eq!(strategy.adapt(1.0, 2, 10), 1.0);
+        // Fewer than a fifth, so shrink it.
+        assert_eq!(strategy.adapt(1.0, 1, 10), 0.5);
+        // But never beyond the bounds.
+        assert_eq!(strategy.adapt(8.0, 10, 10), 8.0);
+        assert_eq!(strategy.adapt(0.25, 0, 10), 0.25);
+    }
+
+    #[test]
+    fn invalid_step_size_bounds() {
+        for (min, max) in [(2.0, 1.0), (0.0, f64::NAN)] {
+            let result = EvolutionStrategy::builder()
+                .min_step_size(min)
+                .max_step_size(max)
+                .genome_maker(StandardUniform.into_collection_generator(8))
+                .mutator(FlipBits)
+                .scorer(FnScorer(count_ones))
+                .inspector(())
+                .build()
+                .search();
+            assert!(matches!(
+                result,
+                Err(EvolutionStrategyError::InvalidStepSizeBounds { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn invalid_initial_step_size() {
+        for initial_step_size in [f64::NAN, f64::INFINITY] {
+            let result = EvolutionStrategy::builder()
+                .initial_step_size(initial_step_size)
+                .genome_maker(StandardUniform.into_collection_generator(8))
+                .mutator(FlipBits)
+                .scorer(FnScorer(count_ones))
+                .inspector(())
+                .build()
+                .search();
+            assert!(matches!(
+                result,
+                Err(EvolutionStrategyError::InvalidInitialStepSize(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn invalid_adaptation_factor() {
+        for adaptation_factor in [f64::NAN, f64::INFINITY, 0.0, -1.22] {
+            let result = EvolutionStrategy::builder()
+                .adaptation_factor(adaptation_factor)
+                .genome_maker(StandardUniform.into_collection_generator(8))
+                .mutator(FlipBits)
+                .scorer(FnScorer(count_ones))
+                .inspector(())
+                .build()
+                .search();
+            assert!(matches!(
+                result,
+                Err(EvolutionStrategyError::InvalidAdaptationFactor(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn plus_strategy_adapts_its_step_size() {
+        let mut step_sizes = StepSizes::default();
+        let report = EvolutionStrategy::builder()
+            .num_to_search(5_001)
+            .num_children_per_step(10)
+            .initial_step_size(4.0)
+            .min_step_size(0.5)
+            .seed(5)
+            .genome_maker(StandardUniform.into_collection_generator(64))
+            .mutator(FlipBits)
+            .scorer(FnScorer(count_ones))
+            .inspector(&mut step_sizes)
+            .build()
+            .search()
+            .unwrap();
+
+        assert_eq!(report.best.map(|best| best.score), Some(Score(64)));
+        let step_sizes = step_sizes.0;
+        assert_eq!(step_sizes.len(), 500);
+        assert_eq!(step_sizes[0], (1, 4.0));
+        // Near the optimum almost every child is worse, so the step size
+        // shrinks to the minimum.
+        assert_eq!(
+            step_sizes.last().map(|(_, step_size)| *step_size),
+            Some(0.5)
+        );
+    }
+}
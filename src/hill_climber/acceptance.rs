@@ -0,0 +1,151 @@
+use std::fmt::Debug;
+
+use crate::numeric_score::NumericScore;
+
+/// Decides whether a [`HillClimber`](super::HillClimber) moves from its current
+/// solution to the best child in each step.
+///
+/// A policy is asked exactly once per step, so policies (like
+/// [`LateAcceptance`]) can keep track of what happened in earlier steps.
+pub trait AcceptancePolicy<Sc>: Debug {
+    /// Should the search move from a solution with score `current` to one
+    /// with score `candidate`?
+    fn accept(&mut self, current: &Sc, candidate: &Sc) -> bool;
+
+    /// Forget about earlier steps, because the climber is starting a new
+    /// climb (see [`Restart`](super::Restart)).
+    fn reset(&mut self) {}
+}
+
+/// Only move to strictly better solutions. This is the default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StrictImprovement;
+
+impl<Sc: PartialOrd> AcceptancePolicy<Sc> for StrictImprovement {
+    fn accept(&mut self, current: &Sc, candidate: &Sc) -> bool {
+        candidate > current
+    }
+}
+
+/// Always move to the best child, even if it's worse than the current solution.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AlwaysReplace;
+
+impl<Sc> AcceptancePolicy<Sc> for AlwaysReplace {
+    fn accept(&mut self, _current: &Sc, _candidate: &Sc) -> bool {
+        true
+    }
+}
+
+/// Move to solutions that are at least as good as the current one, so the
+/// search can drift across plateaus of equally good solutions.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AcceptEqual;
+
+impl<Sc: PartialOrd> AcceptancePolicy<Sc> for AcceptEqual {
+    fn accept(&mut self, current: &Sc, candidate: &Sc) -> bool {
+        candidate >= current
+    }
+}
+
+/// Late-acceptance hill climbing: move to solutions that are at least as good
+/// as the current one, _or_ as the current solution was `length` steps ago.
+///
+/// This lets the search accept some worse solutions without needing a
+/// numeric view of the scores (or a temperature to tune).
+#[derive(Debug, Clone)]
+pub struct LateAcceptance<Sc> {
+    length: usize,
+    step: usize,
+    history: Vec<Sc>,
+}
+
+impl<Sc> LateAcceptance<Sc> {
+    /// Compare to the score of the solution `length` steps ago. A length of 1
+    /// is the same as [`AcceptEqual`].
+    #[must_use]
+    pub fn new(length: usize) -> Self {
+        Self {
+            length: length.max(1),
+            step: 0,
+            history: Vec::new(),
+        }
+    }
+}
+
+impl<Sc> AcceptancePolicy<Sc> for LateAcceptance<Sc>
+where
+    Sc: PartialOrd + Clone + Debug,
+{
+    fn accept(&mut self, current: &Sc, candidate: &Sc) -> bool {
+        // The history starts out full of the first score we see.
+        if self.history.is_empty() {
+            self.history = vec![current.clone(); self.length];
+        }
+        let late = &mut self.history[self.step % self.length];
+        let accepted = candidate >= current || candidate >= &*late;
+        *late = if accepted { candidate } else { current }.clone();
+        self.step += 1;
+        accepted
+    }
+
+    fn reset(&mut self) {
+        self.step = 0;
+        self.history.clear();
+    }
+}
+
+/// Threshold accepting: move to any solution that's no more than `threshold`
+/// worse than the current one. This needs a numeric view of the scores.
+#[derive(Debug, Clone, Copy)]
+pub struct Threshold {
+    pub threshold: f64,
+}
+
+impl<Sc: NumericScore> AcceptancePolicy<Sc> for Threshold {
+    fn accept(&mut self, current: &Sc, candidate: &Sc) -> bool {
+        candidate.to_f64() >= current.to_f64() - self.threshold
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ec_core::test_results::{Error, Score};
+
+    use super::*;
+
+    #[test]
+    fn accept_equal_takes_ties() {
+        assert!(AcceptEqual.accept(&Score(3), &Score(3)));
+        assert!(AcceptEqual.accept(&Error(3), &Error(3)));
+        assert!(!AcceptEqual.accept(&Error(3), &Error(4)));
+        // Unlike the default, which needs a strict improvement.
+        assert!(!StrictImprovement.accept(&Score(3), &Score(3)));
+    }
+
+    #[test]
+    fn threshold_accepts_up_to_the_threshold() {
+        let mut policy = Threshold { threshold: 2.0 };
+        assert!(policy.accept(&Score(10), &Score(8)));
+        assert!(!policy.accept(&Score(10), &Score(7)));
+        // Errors are worse the larger they are.
+        assert!(policy.accept(&Error(10), &Error(12)));
+        assert!(!policy.accept(&Error(10), &Error(13)));
+    }
+
+    #[test]
+    fn late_acceptance_compares_to_earlier_steps() {
+        let mut policy = LateAcceptance::new(2);
+        // The history starts out full of 10s.
+        assert!(!policy.accept(&10, &8));
+        assert!(policy.accept(&10, &12));
+        // Two steps ago the current score was 10, so 11 is good enough.
+        assert!(policy.accept(&12, &11));
+        // Two steps ago the current score was 12.
+        assert!(!policy.accept(&11, &10));
+        assert!(policy.accept(&11, &11));
+
+        policy.reset();
+        assert!(!policy.accept(&5, &4));
+    }
+}
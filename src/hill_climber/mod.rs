@@ -0,0 +1,433 @@
+mod acceptance;
+mod stepper;
+
+pub use acceptance::*;
+pub use stepper::*;
+
+use core::slice;
+use std::{marker::PhantomData, ops::Range};
+
+use bon::Builder;
+use ec_core::{individual::scorer::Scorer, operator::mutator::Mutator};
+use rand::prelude::Distribution;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::{
+    inspector::Inspector,
+    report::{BestFound, ClimbReport, SearchReport},
+    seeding::{seed_or_random, stream_rng},
+    termination::{ProgressTracker, Termination, TerminationReason},
+};
+
+/// A sample number, the genome with that sample number, and its score.
+pub(crate) type Solution<Ge, Sc> = (usize, Ge, Sc);
+
+#[derive(Debug, thiserror::Error)]
+pub enum HillClimberError<MutationError> {
+    #[error(transparent)]
+    Mutation(#[from] MutationError),
+
+    #[error("Processed zero-sized chunk")]
+    ZeroSizedChunk,
+}
+
+/// When a [`HillClimber`] gives up on its current climb and starts a new one
+/// from a fresh genome from `genome_maker`. The best genome across all the
+/// climbs is the one in the search's report.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Restart {
+    /// Keep climbing from the first genome for the whole search.
+    #[default]
+    Never,
+    /// Restart after this many steps in a row that don't find anything better
+    /// than the best genome of the current climb.
+    AfterStagnation(usize),
+    /// Restart once a climb has evaluated this many genomes.
+    EveryNEvaluations(usize),
+}
+
+impl Restart {
+    const fn is_due<Ge, Sc>(self, climb: &Climb<Ge, Sc>) -> bool {
+        match self {
+            Self::Never => false,
+            Self::AfterStagnation(num_steps) => climb.stagnant_steps >= num_steps,
+            Self::EveryNEvaluations(num_evaluations) => climb.evaluations >= num_evaluations,
+        }
+    }
+}
+
+/// The state of the current climb.
+#[derive(Debug)]
+struct Climb<Ge, Sc> {
+    number: usize,
+    first_sample: usize,
+    evaluations: usize,
+    stagnant_steps: usize,
+    current: Solution<Ge, Sc>,
+    best: BestFound<Ge, Sc>,
+}
+
+impl<Ge: Clone, Sc: Clone> Climb<Ge, Sc> {
+    fn report(&self) -> ClimbReport<Ge, Sc> {
+        ClimbReport {
+            climb: self.number,
+            first_sample: self.first_sample,
+            evaluations: self.evaluations,
+            best: self.best.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Builder)]
+pub struct HillClimber<Ge, GM, Mut, Sc, Scr, Ins>
+// You typically wouldn't put all these constraints on the struct itself, instead
+// you'd put them on the `impl` block for the struct. But I'm doing it here to
+// make the constraints more visible and (hopefully) make some of the error
+// messages more helpful for people new to Rust.
+where
+    Ge: Clone + Send + Sync,
+    GM: Distribution<Ge>,
+    Mut: Mutator<Ge> + Sync,
+    Mut::Error: Send,
+    Sc: Ord + PartialOrd + Send,
+    Scr: Scorer<Ge, Score = Sc> + Sync,
+    // The inspector is shown each new current solution as a
+    // (sample number, genome, score) triple.
+    Ins: Inspector<Ge, Sc>,
+{
+    // We need `PhantomData` because `RandomSearch` depends on the type `Ge` but doesn't
+    // actually contain an instance of it. This is a way to tell Rust that `Ge`
+    // is a type that we care about, but we don't actually have an instance of.
+    // The `builder(field)` attribute tells the `Builder` derive macro that this
+    // is a field that is _not_ specified in the build process.
+    #[builder(field)]
+    _p: PhantomData<Ge>,
+
+    #[builder(default = 1_000)]
+    num_to_search: usize,
+
+    #[builder(default = 1)]
+    num_children_per_step: usize,
+
+    /// Mutate and score the children in each step in parallel. The serial and
+    /// parallel searches make the same children, and pick the same best child.
+    #[builder(default = false)]
+    parallel_search: bool,
+
+    /// When to replace the current solution with the best of the "child"
+    /// solutions. By default that's only when the child is better than the
+    /// current solution.
+    #[builder(
+        default = Box::new(StrictImprovement),
+        with = |policy: impl AcceptancePolicy<Sc> + Send + 'static| Box::new(policy)
+    )]
+    acceptance: Box<dyn AcceptancePolicy<Sc> + Send>,
+
+    /// When to give up on the current climb and start again from a new genome.
+    #[builder(default)]
+    restart: Restart,
+
+    /// Additional criteria for stopping the search before `num_to_search`
+    /// genomes have been evaluated.
+    #[builder(default)]
+    termination: Termination<Sc>,
+
+    /// The seed for the random number generator. If this isn't specified a
+    /// random seed is used, which is included in the returned report.
+    seed: Option<u64>,
+
+    genome_maker: GM,
+    mutator: Mut,
+    scorer: Scr,
+    inspector: Ins,
+}
+
+impl<Ge, GM, Mut, Sc, Scr, Ins> HillClimber<Ge, GM, Mut, Sc, Scr, Ins>
+where
+    Ge: Clone + Send + Sync,
+    GM: Distribution<Ge>,
+    Mut: Mutator<Ge> + Sync,
+    Mut::Error: Send,
+    Sc: Ord + PartialOrd + Clone + Send,
+    Scr: Scorer<Ge, Score = Sc> + Sync,
+    // The inspector is shown each new current solution as a
+    // (sample number, genome, score) triple.
+    Ins: Inspector<Ge, Sc>,
+{
+    /// Climb from a randomly generated genome, returning a report with the best
+    /// genome found and the reason the search stopped.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if mutating a genome fails, or if
+    /// `num_children_per_step` is zero.
+    pub fn search(&mut self) -> Result<SearchReport<Ge, Sc>, HillClimberError<Mut::Error>> {
+        let mut stepper = self.stepper()?;
+        while stepper.step()? {}
+        Ok(stepper.finish())
+    }
+
+    /// Climb from `initial_candidate` instead of a randomly generated genome,
+    /// e.g., to refine the best individual from a `Run`. The initial
+    /// candidate is sample number 0.
+    ///
+    /// If there's a [`Restart`] policy, later climbs still start from genomes
+    /// from `genome_maker`.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if mutating a genome fails, or if
+    /// `num_children_per_step` is zero.
+    pub fn search_from(
+        &mut self,
+        initial_candidate: Ge,
+    ) -> Result<SearchReport<Ge, Sc>, HillClimberError<Mut::Error>> {
+        let mut stepper = self.stepper_from(initial_candidate)?;
+        while stepper.step()? {}
+        Ok(stepper.finish())
+    }
+
+    /// Start a climb from a randomly generated genome that the caller runs one
+    /// step at a time. See [`HillClimberStepper`].
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if `num_children_per_step` is zero.
+    #[expect(
+        clippy::type_complexity,
+        reason = "The stepper needs all of the climber's type parameters."
+    )]
+    pub fn stepper(
+        &mut self,
+    ) -> Result<HillClimberStepper<'_, Ge, GM, Mut, Sc, Scr, Ins>, HillClimberError<Mut::Error>>
+    {
+        let seed = seed_or_random(self.seed);
+        let initial_candidate = self.genome_maker.sample(&mut stream_rng(seed, 0));
+        HillClimberStepper::new(self, initial_candidate, seed)
+    }
+
+    /// Like [`HillClimber::stepper`], but climbing from `initial_candidate`
+    /// (see [`HillClimber::search_from`]).
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if `num_children_per_step` is zero.
+    #[expect(
+        clippy::type_complexity,
+        reason = "The stepper needs all of the climber's type parameters."
+    )]
+    pub fn stepper_from(
+        &mut self,
+        initial_candidate: Ge,
+    ) -> Result<HillClimberStepper<'_, Ge, GM, Mut, Sc, Scr, Ins>, HillClimberError<Mut::Error>>
+    {
+        let seed = seed_or_random(self.seed);
+        HillClimberStepper::new(self, initial_candidate, seed)
+    }
+}
+
+/// Mutate and score a child of `parent` for each of the given sample numbers,
+/// in parallel if `parallel` is set. This is the neighborhood of `parent` that
+/// `HillClimber` (and `TabuSearch`) look at in each step.
+///
+/// Each child gets its own RNG, derived from the seed and its sample number,
+/// so the serial and parallel searches make exactly the same children.
+pub(crate) fn sample_neighbors<Ge, Mut, Scr>(
+    mutator: &Mut,
+    scorer: &Scr,
+    parent: &Ge,
+    seed: u64,
+    sample_numbers: Range<usize>,
+    parallel: bool,
+) -> Result<Vec<Solution<Ge, Scr::Score>>, Mut::Error>
+where
+    Ge: Clone + Send + Sync,
+    Mut: Mutator<Ge> + Sync,
+    Mut::Error: Send,
+    Scr: Scorer<Ge> + Sync,
+    Scr::Score: Send,
+{
+    let make_child = |sample_number| {
+        let mut rng = stream_rng(seed, sample_number);
+        let child = mutator.mutate(parent.clone(), &mut rng)?;
+        let score = scorer.score(&child);
+        Ok((sample_number, child, score))
+    };
+    if parallel {
+        sample_numbers.into_par_iter().map(make_child).collect()
+    } else {
+        sample_numbers.map(make_child).collect()
+    }
+}
+
+/// Start a search that moves a single current solution around, like
+/// `SimulatedAnnealing`, `TabuSearch`, and `EvolutionStrategy` do: pick the
+/// seed, tell `inspector` the search has started, and then make, score, and
+/// record the initial solution (sample 0), which is shown to `inspector` as the
+/// first current solution.
+pub(crate) fn start_search<Ge, GM, Scr, Ins>(
+    seed: Option<u64>,
+    genome_maker: &GM,
+    scorer: &Scr,
+    inspector: &mut Ins,
+) -> (ProgressTracker<Ge, Scr::Score>, Solution<Ge, Scr::Score>)
+where
+    Ge: Clone,
+    GM: Distribution<Ge>,
+    Scr: Scorer<Ge>,
+    Scr::Score: PartialOrd + Clone,
+    Ins: Inspector<Ge, Scr::Score>,
+{
+    let seed = seed_or_random(seed);
+    inspector.on_start();
+    let mut tracker = ProgressTracker::new(seed);
+
+    let initial_candidate = genome_maker.sample(&mut stream_rng(seed, 0));
+    let initial_score = scorer.score(&initial_candidate);
+    tracker.record_and_inspect(0, &initial_candidate, &initial_score, inspector);
+    let initial = (0, initial_candidate, initial_score);
+    inspector.on_samples(slice::from_ref(&initial));
+    (tracker, initial)
+}
+
+/// Finish a search started with [`start_search`]. If the search didn't stop
+/// early with `reason`, it either just met one of the `termination` criteria
+/// or ran out of evaluations.
+pub(crate) fn finish_search<Ge, Sc, Ins>(
+    tracker: ProgressTracker<Ge, Sc>,
+    termination: &Termination<Sc>,
+    reason: Option<TerminationReason>,
+    inspector: &mut Ins,
+) -> SearchReport<Ge, Sc>
+where
+    Ge: Clone,
+    Sc: PartialOrd + Clone,
+    Ins: Inspector<Ge, Sc>,
+{
+    let reason = reason
+        .or_else(|| tracker.check(termination))
+        .unwrap_or(TerminationReason::MaxEvaluations);
+    let report = tracker.into_report(reason);
+    inspector.on_finish(&report);
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use ec_core::{
+        distributions::collection::ConvertToCollectionGenerator, individual::scorer::FnScorer,
+        test_results::Score,
+    };
+    use ec_linear::{
+        genome::bitstring::Bitstring, mutator::with_one_over_length::WithOneOverLength,
+    };
+    use rand::distr::StandardUniform;
+
+    use super::*;
+    use crate::{inspector::SearchEvent, test_support::count_ones};
+
+    fn seeded_climb(parallel_search: bool) -> SearchReport<Bitstring, Score<usize>> {
+        HillClimber::builder()
+            .num_to_search(2_000)
+            .num_children_per_step(16)
+            .parallel_search(parallel_search)
+            .seed(7)
+            .genome_maker(StandardUniform.into_collection_generator(64))
+            .mutator(WithOneOverLength)
+            .scorer(FnScorer(count_ones))
+            .inspector(())
+            .build()
+            .search()
+            .unwrap()
+    }
+
+    #[test]
+    fn seeded_serial_and_parallel_climbs_agree() {
+        let serial = seeded_climb(false);
+        let parallel = seeded_climb(true);
+        assert_eq!(serial.evaluations, 2_000);
+        assert_eq!(serial.best, parallel.best);
+    }
+
+    #[test]
+    fn climbers_can_search_on_another_thread() {
+        let mut climber = HillClimber::builder()
+            .num_to_search(100)
+            .acceptance(LateAcceptance::new(5))
+            .seed(7)
+            .genome_maker(StandardUniform.into_collection_generator(64))
+            .mutator(WithOneOverLength)
+            .scorer(FnScorer(count_ones))
+            .inspector(())
+            .build();
+        let report = std::thread::spawn(move || climber.search().unwrap())
+            .join()
+            .unwrap();
+        assert_eq!(report.evaluations, 100);
+    }
+
+    #[test]
+    fn climbing_from_a_given_genome() {
+        let all_ones = Bitstring {
+            bits: vec![true; 64],
+        };
+        let report = HillClimber::builder()
+            .num_to_search(100)
+            .genome_maker(StandardUniform.into_collection_generator(64))
+            .mutator(WithOneOverLength)
+            .scorer(FnScorer(count_ones))
+            .inspector(())
+            .build()
+            .search_from(all_ones.clone())
+            .unwrap();
+        let best = report.best.unwrap();
+        assert_eq!(
+            (best.sample_number, best.genome, best.score),
+            (0, all_ones, Score(64))
+        );
+    }
+
+    #[derive(Default)]
+    struct Climbs(Vec<ClimbReport<Bitstring, Score<usize>>>);
+
+    impl Inspector<Bitstring, Score<usize>> for Climbs {
+        fn on_event(&mut self, event: &SearchEvent<'_, Bitstring, Score<usize>>) {
+            if let SearchEvent::ClimbFinished(climb) = event {
+                self.0.push((*climb).clone());
+            }
+        }
+    }
+
+    #[test]
+    fn restarts_keep_the_global_best() {
+        let mut climbs = Climbs::default();
+        let report = HillClimber::builder()
+            .num_to_search(1_000)
+            .num_children_per_step(9)
+            .restart(Restart::EveryNEvaluations(100))
+            .seed(3)
+            .genome_maker(StandardUniform.into_collection_generator(64))
+            .mutator(WithOneOverLength)
+            .scorer(FnScorer(count_ones))
+            .inspector(&mut climbs)
+            .build()
+            .search()
+            .unwrap();
+
+        let climbs = climbs.0;
+        assert_eq!(climbs.len(), 10);
+        assert!(climbs.iter().all(|climb| climb.evaluations == 100));
+        assert_eq!(
+            climbs.iter().map(|climb| climb.evaluations).sum::<usize>(),
+            report.evaluations
+        );
+        let best_climb = climbs
+            .iter()
+            .map(|climb| &climb.best)
+            .max_by_key(|best| &best.score);
+        assert_eq!(
+            best_climb.map(|best| &best.score),
+            report.best.as_ref().map(|best| &best.score)
+        );
+    }
+}
@@ -0,0 +1,293 @@
+use core::slice;
+
+use ec_core::{individual::scorer::Scorer, operator::mutator::Mutator};
+use rand::prelude::Distribution;
+
+use super::{sample_neighbors, Climb, HillClimber, HillClimberError, Solution};
+use crate::{
+    inspector::{Inspector, SearchEvent},
+    report::{BestFound, SearchReport},
+    seeding::stream_rng,
+    termination::{ProgressTracker, TerminationReason},
+};
+
+/// A [`HillClimber`] search that the caller runs one step at a time, e.g., to
+/// look at the current solution between steps, or to drive the search from a
+/// test or a REPL. Make one with [`HillClimber::stepper`] or
+/// [`HillClimber::stepper_from`].
+///
+/// The inspector sees exactly what it would in [`HillClimber::search`], and
+/// stepping until [`HillClimberStepper::step`] returns `false` gives the same
+/// result as `search`.
+///
+/// # Examples
+///
+/// ```
+/// # use course_helpers::hill_climber::HillClimber;
+/// # use ec_core::{individual::scorer::FnScorer, test_results::Error};
+/// # use ec_core::operator::mutator::Mutator;
+/// # use rand::{distr::Uniform, Rng};
+/// #
+/// # struct Step;
+/// # impl Mutator<i32> for Step {
+/// #     type Error = std::convert::Infallible;
+/// #     fn mutate<R: Rng + ?Sized>(&self, genome: i32, rng: &mut R) -> Result<i32, Self::Error> {
+/// #         Ok(if rng.random() { genome + 1 } else { genome - 1 })
+/// #     }
+/// # }
+/// let mut hill_climber = HillClimber::builder()
+///     .genome_maker(Uniform::new(0, 100).unwrap())
+///     .mutator(Step)
+///     .scorer(FnScorer(|value: &i32| Error(value.abs_diff(42))))
+///     .inspector(())
+///     .build();
+///
+/// let mut stepper = hill_climber.stepper().unwrap();
+/// for _ in 0..10 {
+///     stepper.step().unwrap();
+///     let (sample_number, genome, error) = stepper.current();
+///     println!("Sample {sample_number}: {genome} has error {error}");
+/// }
+/// let report = stepper.finish();
+/// assert_eq!(report.steps, 10);
+/// ```
+#[derive(Debug)]
+pub struct HillClimberStepper<'a, Ge, GM, Mut, Sc, Scr, Ins>
+where
+    Ge: Clone + Send + Sync,
+    GM: Distribution<Ge>,
+    Mut: Mutator<Ge> + Sync,
+    Mut::Error: Send,
+    Sc: Ord + PartialOrd + Clone + Send,
+    Scr: Scorer<Ge, Score = Sc> + Sync,
+    Ins: Inspector<Ge, Sc>,
+{
+    climber: &'a mut HillClimber<Ge, GM, Mut, Sc, Scr, Ins>,
+    seed: u64,
+    tracker: ProgressTracker<Ge, Sc>,
+    climb: Climb<Ge, Sc>,
+    // The sample number of the next genome to evaluate.
+    sample_number: usize,
+    termination_reason: Option<TerminationReason>,
+}
+
+impl<'a, Ge, GM, Mut, Sc, Scr, Ins> HillClimberStepper<'a, Ge, GM, Mut, Sc, Scr, Ins>
+where
+    Ge: Clone + Send + Sync,
+    GM: Distribution<Ge>,
+    Mut: Mutator<Ge> + Sync,
+    Mut::Error: Send,
+    Sc: Ord + PartialOrd + Clone + Send,
+    Scr: Scorer<Ge, Score = Sc> + Sync,
+    Ins: Inspector<Ge, Sc>,
+{
+    pub(super) fn new(
+        climber: &'a mut HillClimber<Ge, GM, Mut, Sc, Scr, Ins>,
+        initial_candidate: Ge,
+        seed: u64,
+    ) -> Result<Self, HillClimberError<Mut::Error>> {
+        if climber.num_children_per_step == 0 {
+            return Err(HillClimberError::ZeroSizedChunk);
+        }
+        climber.inspector.on_start();
+        let mut tracker = ProgressTracker::new(seed);
+        let climb = start_climb(climber, &mut tracker, 0, 0, initial_candidate);
+        Ok(Self {
+            climber,
+            seed,
+            tracker,
+            climb,
+            sample_number: 1,
+            termination_reason: None,
+        })
+    }
+
+    /// Take one step: either mutate and score a batch of
+    /// `num_children_per_step` children of the current solution, or (if the
+    /// [`Restart`](super::Restart) policy says so) start a new climb. Returns
+    /// `false`, without doing anything, if the search is over.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if mutating a genome fails.
+    pub fn step(&mut self) -> Result<bool, HillClimberError<Mut::Error>> {
+        if self.termination_reason.is_none() {
+            self.termination_reason = self.check();
+        }
+        if self.termination_reason.is_some() {
+            return Ok(false);
+        }
+        self.tracker.finish_step();
+
+        // A restart takes a step of its own, in which we evaluate just the
+        // new starting genome. Its RNG comes from its sample number, like
+        // every other genome's.
+        if self.climber.restart.is_due(&self.climb) {
+            self.climber
+                .inspector
+                .on_event(&SearchEvent::ClimbFinished(&self.climb.report()));
+            let genome = self
+                .climber
+                .genome_maker
+                .sample(&mut stream_rng(self.seed, self.sample_number));
+            self.climb = start_climb(
+                self.climber,
+                &mut self.tracker,
+                self.climb.number + 1,
+                self.sample_number,
+                genome,
+            );
+            self.climber.acceptance.reset();
+            self.sample_number += 1;
+            return Ok(true);
+        }
+
+        let climber = &mut *self.climber;
+        let chunk_end = climber
+            .num_to_search
+            .min(self.sample_number + climber.num_children_per_step);
+        let children = sample_neighbors(
+            &climber.mutator,
+            &climber.scorer,
+            &self.climb.current.1,
+            self.seed,
+            self.sample_number..chunk_end,
+            climber.parallel_search,
+        )?;
+        self.climb.evaluations += children.len();
+        self.sample_number = chunk_end;
+
+        // We record the children in order, and (like `max_by`) pick the
+        // last of equally good children, so the serial and parallel
+        // searches always make the same choice.
+        let mut best_in_chunk: Option<Solution<Ge, Sc>> = None;
+        for (sample_number, child, score) in children {
+            self.tracker
+                .record_and_inspect(sample_number, &child, &score, &mut climber.inspector);
+            if best_in_chunk
+                .as_ref()
+                .is_none_or(|(_, _, best_score)| score >= *best_score)
+            {
+                best_in_chunk = Some((sample_number, child, score));
+            }
+        }
+        let best_in_chunk = best_in_chunk.ok_or(HillClimberError::ZeroSizedChunk)?;
+
+        let climb = &mut self.climb;
+        if best_in_chunk.2 > climb.best.score {
+            climb.best = BestFound {
+                genome: best_in_chunk.1.clone(),
+                score: best_in_chunk.2.clone(),
+                sample_number: best_in_chunk.0,
+                step: self.tracker.steps(),
+            };
+            climb.stagnant_steps = 0;
+        } else {
+            climb.stagnant_steps += 1;
+        }
+
+        if climber
+            .acceptance
+            .accept(&climb.current.2, &best_in_chunk.2)
+        {
+            climb.current = best_in_chunk;
+            climber
+                .inspector
+                .on_samples(slice::from_ref(&climb.current));
+        }
+        Ok(true)
+    }
+
+    /// Why the search would stop now, if it would.
+    fn check(&self) -> Option<TerminationReason> {
+        self.tracker.check(&self.climber.termination).or_else(|| {
+            (self.sample_number >= self.climber.num_to_search)
+                .then_some(TerminationReason::MaxEvaluations)
+        })
+    }
+
+    /// The current solution, as a `(sample_number, genome, score)` triple.
+    pub const fn current(&self) -> &(usize, Ge, Sc) {
+        &self.climb.current
+    }
+
+    /// The best genome found so far, in any climb.
+    pub const fn best(&self) -> Option<&BestFound<Ge, Sc>> {
+        self.tracker.best()
+    }
+
+    /// The number of steps taken so far.
+    pub const fn steps(&self) -> usize {
+        self.tracker.steps()
+    }
+
+    /// The number of genomes evaluated so far.
+    pub const fn evaluations(&self) -> usize {
+        self.tracker.evaluations()
+    }
+
+    /// Why the search stopped, once [`HillClimberStepper::step`] has returned
+    /// `false`.
+    pub const fn termination_reason(&self) -> Option<&TerminationReason> {
+        self.termination_reason.as_ref()
+    }
+
+    /// End the search, returning the same report as [`HillClimber::search`].
+    /// If the search would have kept going, the termination reason is
+    /// [`TerminationReason::Stopped`].
+    pub fn finish(self) -> SearchReport<Ge, Sc> {
+        let reason = self
+            .termination_reason
+            .clone()
+            .or_else(|| self.check())
+            .unwrap_or(TerminationReason::Stopped);
+        self.climber
+            .inspector
+            .on_event(&SearchEvent::ClimbFinished(&self.climb.report()));
+        let report = self.tracker.into_report(reason);
+        self.climber.inspector.on_finish(&report);
+        report
+    }
+}
+
+/// Score `initial_candidate` and start climb number `number` from it.
+fn start_climb<Ge, GM, Mut, Sc, Scr, Ins>(
+    climber: &mut HillClimber<Ge, GM, Mut, Sc, Scr, Ins>,
+    tracker: &mut ProgressTracker<Ge, Sc>,
+    number: usize,
+    sample_number: usize,
+    initial_candidate: Ge,
+) -> Climb<Ge, Sc>
+where
+    Ge: Clone + Send + Sync,
+    GM: Distribution<Ge>,
+    Mut: Mutator<Ge> + Sync,
+    Mut::Error: Send,
+    Sc: Ord + PartialOrd + Clone + Send,
+    Scr: Scorer<Ge, Score = Sc> + Sync,
+    Ins: Inspector<Ge, Sc>,
+{
+    let initial_score = climber.scorer.score(&initial_candidate);
+    tracker.record_and_inspect(
+        sample_number,
+        &initial_candidate,
+        &initial_score,
+        &mut climber.inspector,
+    );
+    let current = (sample_number, initial_candidate, initial_score);
+    climber.inspector.on_samples(slice::from_ref(&current));
+
+    Climb {
+        number,
+        first_sample: sample_number,
+        evaluations: 1,
+        stagnant_steps: 0,
+        best: BestFound {
+            genome: current.1.clone(),
+            score: current.2.clone(),
+            sample_number,
+            step: tracker.steps(),
+        },
+        current,
+    }
+}
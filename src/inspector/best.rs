@@ -1,7 +1,14 @@
 use std::fmt::Display;
 
+use bon::Builder;
+
+use super::Inspector;
+
 /// Updates `current_best` with any better solution found in `candidate_solutions`.
-/// One solution is better than another if its score is higher.
+/// One solution is better than another if its score is higher. (`ec_core`'s
+/// `Error` type orders lower errors as "higher", so this keeps the solution
+/// with the lowest error.) Use a [`BestTracker`] to say explicitly whether to
+/// maximize or minimize scores.
 ///
 /// # Examples
 ///
@@ -54,13 +61,195 @@ where
     Score: Display,
 {
     println!(
-        "New best solution found:  {:25} with error {:25} at sample number {:25}",
+        "New best solution found:  {:25} with score {:25} at sample number {:25}",
         genome, score, sample_number
     );
 }
 
+/// Whether higher or lower scores are better.
+///
+/// Note that `ec_core`'s `Error` type already orders lower errors as "higher",
+/// so [`Direction::Maximize`] is the right choice for `Error`s (and
+/// `TestResults` of `Error`s). [`Direction::Minimize`] is for plain numeric
+/// scores where lower is better, like a raw `u32` distance.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Direction {
+    #[default]
+    Maximize,
+    Minimize,
+}
+
+impl Direction {
+    /// Is `first` strictly better than `second`?
+    pub fn is_better<Sc: PartialOrd>(self, first: &Sc, second: &Sc) -> bool {
+        match self {
+            Self::Maximize => first > second,
+            Self::Minimize => first < second,
+        }
+    }
+}
+
+/// A function that reports a new best `(sample_number, genome, score)`
+/// solution, like [`print_best`].
+pub type Printer<Ge, Sc> = fn(&(usize, Ge, Sc));
+
+/// Keeps track of the best solution (or the best `top_k` solutions) seen by a
+/// search, and the trajectory of improvements to the best solution.
+///
+/// Solutions are `(sample_number, genome, score)` triples, like the ones passed
+/// to [`Inspector::on_samples`], which is how a `BestTracker` is typically
+/// fed when it's used as (part of) an inspector for `RandomSearch` or
+/// `HillClimber`. Solutions that score the same are kept in the order they're
+/// recorded in, and a solution that ties the current best isn't an improvement.
+///
+/// # Examples
+///
+/// Track the three lowest distances (and any ties with the third lowest) in a
+/// search where lower is better.
+///
+/// ```
+/// # use course_helpers::inspector::{BestTracker, Direction};
+/// #
+/// let mut tracker = BestTracker::builder()
+///     .direction(Direction::Minimize)
+///     .top_k(3)
+///     .keep_ties(true)
+///     .build();
+/// tracker.record_all(&[(0, "a", 9), (1, "b", 4), (2, "c", 7), (3, "d", 4), (4, "e", 7), (5, "f", 1)]);
+///
+/// assert_eq!(tracker.best(), Some(&(5, "f", 1)));
+/// let top = tracker.top().iter().map(|(_, genome, _)| *genome).collect::<Vec<_>>();
+/// assert_eq!(top, ["f", "b", "d"]);
+/// let improvements = tracker.trajectory().iter().map(|(number, _, _)| *number).collect::<Vec<_>>();
+/// assert_eq!(improvements, [0, 1, 5]);
+/// ```
+#[derive(Debug, Clone, Builder)]
+pub struct BestTracker<Ge, Sc> {
+    #[builder(default)]
+    direction: Direction,
+
+    /// How many of the best solutions to keep.
+    #[builder(default = 1)]
+    top_k: usize,
+
+    /// Keep solutions that tie the worst of the `top_k` best, even if that
+    /// means keeping more than `top_k` solutions.
+    #[builder(default = false)]
+    keep_ties: bool,
+
+    /// Called with each new best solution, e.g., [`print_best`] to print it.
+    printer: Option<Printer<Ge, Sc>>,
+
+    #[builder(skip)]
+    top: Vec<(usize, Ge, Sc)>,
+
+    #[builder(skip)]
+    trajectory: Vec<(usize, Ge, Sc)>,
+}
+
+impl<Ge, Sc> BestTracker<Ge, Sc>
+where
+    Ge: Clone,
+    Sc: PartialOrd + Clone,
+{
+    /// Record one solution, returning `true` if it's a new best.
+    pub fn record(&mut self, sample_number: usize, genome: &Ge, score: &Sc) -> bool {
+        // Most solutions in a long search don't make the cut, so we check
+        // before cloning them. A new best always makes the cut.
+        let kept = self
+            .top
+            .get(self.top_k.max(1) - 1)
+            .is_none_or(|(_, _, cutoff)| {
+                if self.keep_ties {
+                    !self.direction.is_better(cutoff, score)
+                } else {
+                    self.direction.is_better(score, cutoff)
+                }
+            });
+        if !kept {
+            return false;
+        }
+        let solution = (sample_number, genome.clone(), score.clone());
+
+        let improved = self
+            .best()
+            .is_none_or(|(_, _, best_score)| self.direction.is_better(score, best_score));
+        if improved {
+            if let Some(printer) = self.printer {
+                printer(&solution);
+            }
+            self.trajectory.push(solution.clone());
+        }
+
+        // Insert after every solution that's at least as good, so ties stay in
+        // the order they were recorded in.
+        let position = self
+            .top
+            .iter()
+            .position(|(_, _, other)| self.direction.is_better(score, other))
+            .unwrap_or(self.top.len());
+        self.top.insert(position, solution);
+        self.truncate();
+
+        improved
+    }
+
+    /// Record every solution in `solution_chunk`.
+    pub fn record_all(&mut self, solution_chunk: &[(usize, Ge, Sc)]) {
+        for (sample_number, genome, score) in solution_chunk {
+            self.record(*sample_number, genome, score);
+        }
+    }
+
+    /// Drop any solutions beyond the best `top_k` (apart from ties, if we're
+    /// keeping them).
+    fn truncate(&mut self) {
+        let Some((_, _, cutoff)) = self.top.get(self.top_k.max(1) - 1) else {
+            return;
+        };
+        let keep = if self.keep_ties {
+            let cutoff = cutoff.clone();
+            self.top
+                .iter()
+                .take_while(|(_, _, score)| !self.direction.is_better(&cutoff, score))
+                .count()
+        } else {
+            self.top_k.max(1)
+        };
+        self.top.truncate(keep);
+    }
+
+    /// The best solution seen so far (the first one, if there are ties).
+    pub fn best(&self) -> Option<&(usize, Ge, Sc)> {
+        self.top.first()
+    }
+
+    /// The best `top_k` solutions seen so far (plus any ties, if we're keeping
+    /// them), best first.
+    pub fn top(&self) -> &[(usize, Ge, Sc)] {
+        &self.top
+    }
+
+    /// Every solution that was a new best when it was recorded, in order.
+    pub fn trajectory(&self) -> &[(usize, Ge, Sc)] {
+        &self.trajectory
+    }
+}
+
+impl<Ge, Sc> Inspector<Ge, Sc> for BestTracker<Ge, Sc>
+where
+    Ge: Clone,
+    Sc: PartialOrd + Clone,
+{
+    fn on_samples(&mut self, solution_chunk: &[(usize, Ge, Sc)]) {
+        self.record_all(solution_chunk);
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::cell::Cell;
+
     use super::*;
 
     #[test]
@@ -75,4 +264,55 @@ mod tests {
         update_best(&mut best, &second_chunk);
         assert_eq!(best, Some((4, "e", 11)));
     }
+
+    #[test]
+    fn minimizing_without_ties() {
+        let mut tracker = BestTracker::builder()
+            .direction(Direction::Minimize)
+            .top_k(2)
+            .build();
+        tracker.record_all(&[(0, "a", 5), (1, "b", 3), (2, "c", 3), (3, "d", 8)]);
+        assert_eq!(tracker.top(), [(1, "b", 3), (2, "c", 3)]);
+        assert_eq!(tracker.trajectory(), [(0, "a", 5), (1, "b", 3)]);
+    }
+
+    #[test]
+    fn only_solutions_that_make_the_cut_are_cloned() {
+        /// Counts how many times it's been cloned.
+        #[derive(Debug)]
+        struct CountClones<'a>(&'a Cell<usize>);
+
+        impl Clone for CountClones<'_> {
+            fn clone(&self) -> Self {
+                self.0.set(self.0.get() + 1);
+                Self(self.0)
+            }
+        }
+
+        let num_clones = Cell::new(0);
+        let mut tracker = BestTracker::builder().top_k(2).build();
+        for (sample_number, score) in [5, 8, 3, 8, 2, 9, 1].into_iter().enumerate() {
+            tracker.record(sample_number, &CountClones(&num_clones), &score);
+        }
+        // Only 5, 8, the second 8, and 9 make the top two when they're
+        // recorded. 5, 8, and 9 were new bests, so they were cloned again for
+        // the trajectory.
+        assert_eq!(num_clones.get(), 7);
+        let top = tracker
+            .top()
+            .iter()
+            .map(|(number, _, _)| *number)
+            .collect::<Vec<_>>();
+        assert_eq!(top, [5, 1]);
+    }
+
+    #[test]
+    fn tracking_genomes_that_cant_be_displayed() {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Opaque(u8);
+
+        let mut tracker = BestTracker::builder().build();
+        tracker.record_all(&[(0, Opaque(1), 5), (1, Opaque(2), 8), (2, Opaque(3), 6)]);
+        assert_eq!(tracker.best(), Some(&(1, Opaque(2), 8)));
+    }
 }
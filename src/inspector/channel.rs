@@ -0,0 +1,164 @@
+use ec_core::individual::ec::EcIndividual;
+use flume::{Receiver, Sender, TrySendError};
+
+use super::Inspector;
+use crate::ec_run::Population;
+
+/// What a [`Forwarder`] does when its channel is full.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WhenFull {
+    /// Wait for the consumer to catch up. Nothing is lost, but a slow consumer
+    /// slows down the search.
+    #[default]
+    Block,
+    /// Drop the message (and count it in [`Forwarder::num_dropped`]), so the
+    /// search never waits for the consumer.
+    Drop,
+}
+
+/// A copy of a `Run` population, as sent by [`Forwarder::forward_generation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenerationSnapshot<G, Sc> {
+    pub generation: usize,
+    pub population: Vec<EcIndividual<G, Sc>>,
+}
+
+/// An inspector that forwards what it sees over a bounded `flume` channel, so
+/// a consumer thread can do expensive logging or visualization without holding
+/// up the search (or, in `RandomSearch`'s parallel mode, holding the lock
+/// around the inspector).
+///
+/// # Examples
+///
+/// Forward `RandomSearch` (or `HillClimber`) solution chunks to a thread that
+/// keeps track of the best solution.
+///
+/// ```
+/// # use course_helpers::inspector::{Forwarder, WhenFull, update_best};
+/// #
+/// let (mut forwarder, receiver) = Forwarder::<Vec<_>>::bounded(16, WhenFull::Block);
+/// let consumer = std::thread::spawn(move || {
+///     let mut best = None;
+///     for solution_chunk in receiver {
+///         update_best(&mut best, &solution_chunk);
+///     }
+///     best
+/// });
+///
+/// let mut inspector = |solution_chunk: &[(usize, &'static str, i32)]| forwarder.forward_samples(solution_chunk);
+/// inspector(&[(0, "a", 5), (1, "b", 8)]);
+/// inspector(&[(2, "c", 7)]);
+/// // Dropping the forwarder closes the channel, which ends the consumer's loop.
+/// drop(forwarder);
+///
+/// assert_eq!(consumer.join().unwrap(), Some((1, "b", 8)));
+/// ```
+#[derive(Debug)]
+pub struct Forwarder<T> {
+    sender: Sender<T>,
+    when_full: WhenFull,
+    num_dropped: usize,
+}
+
+impl<T> Forwarder<T> {
+    /// A forwarder whose channel holds up to `capacity` messages, and the
+    /// receiving end of that channel.
+    #[must_use]
+    pub fn bounded(capacity: usize, when_full: WhenFull) -> (Self, Receiver<T>) {
+        let (sender, receiver) = flume::bounded(capacity);
+        let forwarder = Self {
+            sender,
+            when_full,
+            num_dropped: 0,
+        };
+        (forwarder, receiver)
+    }
+
+    /// Send `message` to the consumer.
+    ///
+    /// If the receiver has been dropped there's nobody to send to, so the
+    /// message is quietly discarded.
+    pub fn forward(&mut self, message: T) {
+        match self.when_full {
+            WhenFull::Block => {
+                let _ = self.sender.send(message);
+            }
+            WhenFull::Drop => {
+                if let Err(TrySendError::Full(_)) = self.sender.try_send(message) {
+                    self.num_dropped += 1;
+                }
+            }
+        }
+    }
+
+    /// The number of messages dropped because the channel was full.
+    #[must_use]
+    pub const fn num_dropped(&self) -> usize {
+        self.num_dropped
+    }
+}
+
+impl<Ge, Sc> Forwarder<Vec<(usize, Ge, Sc)>>
+where
+    Ge: Clone,
+    Sc: Clone,
+{
+    /// Forward a chunk of `(sample_number, genome, score)` solutions, as seen
+    /// by a `RandomSearch` or `HillClimber` inspector.
+    pub fn forward_samples(&mut self, solution_chunk: &[(usize, Ge, Sc)]) {
+        self.forward(solution_chunk.to_vec());
+    }
+}
+
+impl<G, Sc> Forwarder<GenerationSnapshot<G, Sc>>
+where
+    G: Clone,
+    Sc: Clone,
+{
+    /// Forward a copy of a `Run` population.
+    pub fn forward_generation(&mut self, generation: usize, population: &[EcIndividual<G, Sc>]) {
+        self.forward(GenerationSnapshot {
+            generation,
+            population: population.to_vec(),
+        });
+    }
+}
+
+impl<Ge, Sc> Inspector<Ge, Sc> for Forwarder<Vec<(usize, Ge, Sc)>>
+where
+    Ge: Clone,
+    Sc: Clone,
+{
+    fn on_samples(&mut self, solution_chunk: &[(usize, Ge, Sc)]) {
+        self.forward_samples(solution_chunk);
+    }
+}
+
+impl<G, Sc> Inspector<G, Sc> for Forwarder<GenerationSnapshot<G, Sc>>
+where
+    G: Clone,
+    Sc: Clone,
+{
+    fn on_generation(&mut self, generation: usize, population: &Population<G, Sc>) {
+        self.forward_generation(generation, population);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dropping_when_full() {
+        let (mut forwarder, receiver) = Forwarder::bounded(2, WhenFull::Drop);
+        for generation in 0..5 {
+            forwarder.forward_generation(generation, &[EcIndividual::new("a", 1)]);
+        }
+        assert_eq!(forwarder.num_dropped(), 3);
+        let generations = receiver
+            .drain()
+            .map(|snapshot| snapshot.generation)
+            .collect::<Vec<_>>();
+        assert_eq!(generations, [0, 1]);
+    }
+}
@@ -0,0 +1,262 @@
+use crate::{
+    ec_run::Population,
+    report::{BestFound, ClimbReport, SearchReport},
+};
+
+/// Watches a search as it runs, e.g., to print progress, log to a file, or
+/// collect statistics.
+///
+/// Every hook does nothing by default, so an inspector only has to implement
+/// the ones it cares about. Which hooks get called depends on the search:
+/// `RandomSearch`, `HillClimber`, and the other searches that work with one
+/// solution at a time call [`Inspector::on_samples`], while `Run` calls
+/// [`Inspector::on_generation`]. Searches report anything that's specific to
+/// them as a [`SearchEvent`] via [`Inspector::on_event`]. They all call
+/// [`Inspector::on_start`], [`Inspector::on_new_best`], and
+/// [`Inspector::on_finish`].
+///
+/// Tuples of inspectors are inspectors that call each of their elements in
+/// turn, so `(Statistics::new(), logger)` collects statistics _and_ logs to
+/// a file. Plain closures can be used with the [`on_samples`] and
+/// [`on_generation`] adapters.
+pub trait Inspector<Ge, Sc> {
+    /// Called once, before the search evaluates anything.
+    fn on_start(&mut self) {}
+
+    /// Called with a batch of `(sample_number, genome, score)` solutions.
+    fn on_samples(&mut self, _solution_chunk: &[(usize, Ge, Sc)]) {}
+
+    /// Called with the population at the start of each generation.
+    fn on_generation(&mut self, _generation: usize, _population: &Population<Ge, Sc>) {}
+
+    /// Called with the population of one island at the start of each
+    /// generation in an island run. By default this just calls
+    /// [`Inspector::on_generation`].
+    fn on_island_generation(
+        &mut self,
+        _island: usize,
+        generation: usize,
+        population: &Population<Ge, Sc>,
+    ) {
+        self.on_generation(generation, population);
+    }
+
+    /// Called every time the search finds a new best solution.
+    fn on_new_best(&mut self, _best: &BestFound<Ge, Sc>) {}
+
+    /// Called with events that only some searches produce; see
+    /// [`SearchEvent`].
+    fn on_event(&mut self, _event: &SearchEvent<'_, Ge, Sc>) {}
+
+    /// Called once, after the search has stopped.
+    fn on_finish(&mut self, _report: &SearchReport<Ge, Sc>) {}
+}
+
+/// Something that happened in a particular kind of search, passed to
+/// [`Inspector::on_event`].
+///
+/// New kinds of search add their own variants here rather than new hooks on
+/// [`Inspector`], so inspectors should ignore the events they don't know
+/// about.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SearchEvent<'a, Ge, Sc> {
+    /// A climb in a `HillClimber` search ended, either because the climber
+    /// restarted or because the search stopped.
+    ClimbFinished(&'a ClimbReport<Ge, Sc>),
+    /// An `EvolutionStrategy` is starting a step with this step size (e.g.,
+    /// mutation rate).
+    StepSize { step: usize, step_size: f64 },
+}
+
+/// The inspector that doesn't do anything.
+impl<Ge, Sc> Inspector<Ge, Sc> for () {}
+
+impl<Ge, Sc, I> Inspector<Ge, Sc> for &mut I
+where
+    I: Inspector<Ge, Sc> + ?Sized,
+{
+    fn on_start(&mut self) {
+        (**self).on_start();
+    }
+
+    fn on_samples(&mut self, solution_chunk: &[(usize, Ge, Sc)]) {
+        (**self).on_samples(solution_chunk);
+    }
+
+    fn on_generation(&mut self, generation: usize, population: &Population<Ge, Sc>) {
+        (**self).on_generation(generation, population);
+    }
+
+    fn on_island_generation(
+        &mut self,
+        island: usize,
+        generation: usize,
+        population: &Population<Ge, Sc>,
+    ) {
+        (**self).on_island_generation(island, generation, population);
+    }
+
+    fn on_new_best(&mut self, best: &BestFound<Ge, Sc>) {
+        (**self).on_new_best(best);
+    }
+
+    fn on_event(&mut self, event: &SearchEvent<'_, Ge, Sc>) {
+        (**self).on_event(event);
+    }
+
+    fn on_finish(&mut self, report: &SearchReport<Ge, Sc>) {
+        (**self).on_finish(report);
+    }
+}
+
+macro_rules! impl_inspector_for_tuple {
+    ($($inspector:ident),+) => {
+        impl<Ge, Sc, $($inspector),+> Inspector<Ge, Sc> for ($($inspector,)+)
+        where
+            $($inspector: Inspector<Ge, Sc>),+
+        {
+            fn on_start(&mut self) {
+                #[expect(non_snake_case, reason = "The names come from the type parameters.")]
+                let ($($inspector,)+) = self;
+                $($inspector.on_start();)+
+            }
+
+            fn on_samples(&mut self, solution_chunk: &[(usize, Ge, Sc)]) {
+                #[expect(non_snake_case, reason = "The names come from the type parameters.")]
+                let ($($inspector,)+) = self;
+                $($inspector.on_samples(solution_chunk);)+
+            }
+
+            fn on_generation(&mut self, generation: usize, population: &Population<Ge, Sc>) {
+                #[expect(non_snake_case, reason = "The names come from the type parameters.")]
+                let ($($inspector,)+) = self;
+                $($inspector.on_generation(generation, population);)+
+            }
+
+            fn on_island_generation(
+                &mut self,
+                island: usize,
+                generation: usize,
+                population: &Population<Ge, Sc>,
+            ) {
+                #[expect(non_snake_case, reason = "The names come from the type parameters.")]
+                let ($($inspector,)+) = self;
+                $($inspector.on_island_generation(island, generation, population);)+
+            }
+
+            fn on_new_best(&mut self, best: &BestFound<Ge, Sc>) {
+                #[expect(non_snake_case, reason = "The names come from the type parameters.")]
+                let ($($inspector,)+) = self;
+                $($inspector.on_new_best(best);)+
+            }
+
+            fn on_event(&mut self, event: &SearchEvent<'_, Ge, Sc>) {
+                #[expect(non_snake_case, reason = "The names come from the type parameters.")]
+                let ($($inspector,)+) = self;
+                $($inspector.on_event(event);)+
+            }
+
+            fn on_finish(&mut self, report: &SearchReport<Ge, Sc>) {
+                #[expect(non_snake_case, reason = "The names come from the type parameters.")]
+                let ($($inspector,)+) = self;
+                $($inspector.on_finish(report);)+
+            }
+        }
+    };
+}
+
+impl_inspector_for_tuple!(A);
+impl_inspector_for_tuple!(A, B);
+impl_inspector_for_tuple!(A, B, C);
+impl_inspector_for_tuple!(A, B, C, D);
+
+/// An [`Inspector`] that passes each batch of solutions to a closure. See
+/// [`on_samples`].
+#[derive(Debug, Clone)]
+pub struct OnSamples<F>(F);
+
+/// Use a closure that takes a slice of `(sample_number, genome, score)`
+/// solutions as an [`Inspector`] for `RandomSearch` or `HillClimber`.
+///
+/// # Examples
+///
+/// ```
+/// # use course_helpers::inspector::{on_samples, update_best};
+/// #
+/// let mut best = None;
+/// let inspector = on_samples(|solution_chunk| update_best(&mut best, solution_chunk));
+/// # let _: &dyn course_helpers::inspector::Inspector<i32, i32> = &inspector;
+/// ```
+pub const fn on_samples<Ge, Sc, F>(inspect: F) -> OnSamples<F>
+where
+    F: FnMut(&[(usize, Ge, Sc)]),
+{
+    OnSamples(inspect)
+}
+
+impl<Ge, Sc, F> Inspector<Ge, Sc> for OnSamples<F>
+where
+    F: FnMut(&[(usize, Ge, Sc)]),
+{
+    fn on_samples(&mut self, solution_chunk: &[(usize, Ge, Sc)]) {
+        (self.0)(solution_chunk);
+    }
+}
+
+/// An [`Inspector`] that passes the generation number and population to a
+/// closure. See [`on_generation`].
+#[derive(Debug, Clone)]
+pub struct OnGeneration<F>(F);
+
+/// Use a closure that takes the generation number and population as an
+/// [`Inspector`] for `Run`.
+pub const fn on_generation<G, Sc, F>(inspect: F) -> OnGeneration<F>
+where
+    F: FnMut(usize, &Population<G, Sc>),
+{
+    OnGeneration(inspect)
+}
+
+impl<G, Sc, F> Inspector<G, Sc> for OnGeneration<F>
+where
+    F: FnMut(usize, &Population<G, Sc>),
+{
+    fn on_generation(&mut self, generation: usize, population: &Population<G, Sc>) {
+        (self.0)(generation, population);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct CountStarts(usize);
+
+    impl Inspector<&str, i32> for CountStarts {
+        fn on_start(&mut self) {
+            self.0 += 1;
+        }
+    }
+
+    #[test]
+    fn tuples_call_every_inspector() {
+        let mut seen = Vec::new();
+        let mut starts = CountStarts::default();
+        {
+            let mut inspector = (
+                &mut starts,
+                on_samples(|solution_chunk: &[(usize, &str, i32)]| {
+                    seen.extend(solution_chunk.iter().map(|(number, _, _)| *number));
+                }),
+                (),
+            );
+            inspector.on_start();
+            inspector.on_samples(&[(0, "a", 1), (1, "b", 2)]);
+            inspector.on_generation(0, &Vec::new());
+        }
+        assert_eq!(starts.0, 1);
+        assert_eq!(seen, [0, 1]);
+    }
+}
@@ -0,0 +1,442 @@
+use std::{
+    fmt::Display,
+    io::{self, Write},
+    time::Instant,
+};
+
+use ec_core::individual::ec::EcIndividual;
+
+use super::{statistics::score_quantiles, Inspector};
+use crate::{
+    ec_run::Population,
+    report::{BestFound, SearchReport},
+};
+
+/// The format of the records written by [`ImprovementLogger`] and [`GenerationLogger`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Comma-separated values, with a header line before the first record.
+    Csv,
+    /// One JSON object per line.
+    JsonLines,
+}
+
+/// Writes a record every time a better solution turns up. As an inspector it
+/// writes a record for each new best the search reports, so it works with any
+/// search. As elsewhere in this crate, higher scores are better.
+///
+/// Each record has the sample number, the number of seconds since the logger
+/// was created, the score, and the genome (as text, using `Display`).
+///
+/// # Examples
+///
+/// ```
+/// # use course_helpers::inspector::{ImprovementLogger, LogFormat};
+/// #
+/// let mut logger = ImprovementLogger::new(Vec::new(), LogFormat::Csv);
+/// let mut inspector = |solution_chunk: &[(usize, &str, i32)]| {
+///     logger.log_improvements(solution_chunk).expect("Writing to a `Vec` can't fail");
+/// };
+///
+/// inspector(&[(0, "a", 5), (1, "b", 8), (2, "c", 7)]);
+/// inspector(&[(3, "d", 2)]);
+///
+/// let log = String::from_utf8(logger.into_inner()).unwrap();
+/// let scores = log.lines().map(|line| line.split(',').nth(2).unwrap()).collect::<Vec<_>>();
+/// assert_eq!(scores, ["score", "5", "8"]);
+/// ```
+#[derive(Debug)]
+pub struct ImprovementLogger<W, Sc> {
+    records: RecordWriter<W>,
+    start: Instant,
+    best: Option<Sc>,
+}
+
+impl<W, Sc> ImprovementLogger<W, Sc> {
+    /// The first error from writing (or, when the search finishes, flushing)
+    /// the records when this is used as an [`Inspector`] (which can't return
+    /// errors). No more records are written after an error.
+    pub const fn error(&self) -> Option<&io::Error> {
+        self.records.error.as_ref()
+    }
+}
+
+impl<W, Sc> ImprovementLogger<W, Sc>
+where
+    W: Write,
+    Sc: PartialOrd + Clone + Display,
+{
+    pub fn new(writer: W, format: LogFormat) -> Self {
+        Self {
+            records: RecordWriter::new(writer, format),
+            start: Instant::now(),
+            best: None,
+        }
+    }
+
+    /// Write a record for each solution in `solution_chunk` that's better than
+    /// every solution seen before it.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if writing to the underlying writer fails.
+    pub fn log_improvements<Ge>(&mut self, solution_chunk: &[(usize, Ge, Sc)]) -> io::Result<()>
+    where
+        Ge: Display,
+    {
+        for (sample_number, genome, score) in solution_chunk {
+            if self.best.as_ref().is_some_and(|best| score <= best) {
+                continue;
+            }
+            self.log_improvement(*sample_number, genome, score)?;
+        }
+        Ok(())
+    }
+
+    fn log_improvement<Ge>(
+        &mut self,
+        sample_number: usize,
+        genome: &Ge,
+        score: &Sc,
+    ) -> io::Result<()>
+    where
+        Ge: Display,
+    {
+        self.best = Some(score.clone());
+        self.records.write(&[
+            ("sample_number", Value::Number(sample_number.to_string())),
+            (
+                "elapsed_seconds",
+                Value::Number(self.start.elapsed().as_secs_f64().to_string()),
+            ),
+            ("score", Value::Text(score.to_string())),
+            ("genome", Value::Text(genome.to_string())),
+        ])
+    }
+
+    /// Get back the underlying writer, e.g., to flush it or (in tests) look at
+    /// what was written.
+    pub fn into_inner(self) -> W {
+        self.records.writer
+    }
+}
+
+/// Writes a record summarizing each generation of a `Run`, so it can be used
+/// as (or from) a `Run` inspector.
+///
+/// Each record has the generation number, the number of seconds since the
+/// logger was created, the population size, the best, median, and worst
+/// scores, and the best genome (as text, using `Display`).
+#[derive(Debug)]
+pub struct GenerationLogger<W> {
+    records: RecordWriter<W>,
+    start: Instant,
+}
+
+impl<W> GenerationLogger<W> {
+    /// The first error from writing (or, when the run finishes, flushing)
+    /// the records when this is used as an [`Inspector`] (which can't return
+    /// errors). No more records are written after an error.
+    pub const fn error(&self) -> Option<&io::Error> {
+        self.records.error.as_ref()
+    }
+}
+
+impl<W> GenerationLogger<W>
+where
+    W: Write,
+{
+    pub fn new(writer: W, format: LogFormat) -> Self {
+        Self {
+            records: RecordWriter::new(writer, format),
+            start: Instant::now(),
+        }
+    }
+
+    /// Write a record summarizing `population`.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if writing to the underlying writer fails.
+    pub fn log_generation<G, Sc>(
+        &mut self,
+        generation: usize,
+        population: &[EcIndividual<G, Sc>],
+    ) -> io::Result<()>
+    where
+        G: Display,
+        Sc: Ord + Clone + Display,
+    {
+        let quantiles = score_quantiles(population);
+        let score = |pick: fn(&_) -> &Sc| {
+            quantiles.as_ref().map_or(Value::Null, |quantiles| {
+                Value::Text(pick(quantiles).to_string())
+            })
+        };
+        let best_genome = population
+            .iter()
+            .max_by(|first, second| first.test_results.cmp(&second.test_results))
+            .map_or(Value::Null, |best| Value::Text(best.genome.to_string()));
+
+        self.records.write(&[
+            ("generation", Value::Number(generation.to_string())),
+            (
+                "elapsed_seconds",
+                Value::Number(self.start.elapsed().as_secs_f64().to_string()),
+            ),
+            (
+                "population_size",
+                Value::Number(population.len().to_string()),
+            ),
+            ("best_score", score(|quantiles| &quantiles.best)),
+            ("median_score", score(|quantiles| &quantiles.median)),
+            ("worst_score", score(|quantiles| &quantiles.worst)),
+            ("best_genome", best_genome),
+        ])
+    }
+
+    /// Get back the underlying writer, e.g., to flush it or (in tests) look at
+    /// what was written.
+    pub fn into_inner(self) -> W {
+        self.records.writer
+    }
+}
+
+impl<W, Ge, Sc> Inspector<Ge, Sc> for ImprovementLogger<W, Sc>
+where
+    W: Write,
+    Ge: Display,
+    Sc: PartialOrd + Clone + Display,
+{
+    fn on_new_best(&mut self, best: &BestFound<Ge, Sc>) {
+        if self.records.error.is_none() {
+            self.records.error = self
+                .log_improvement(best.sample_number, &best.genome, &best.score)
+                .err();
+        }
+    }
+
+    fn on_finish(&mut self, _report: &SearchReport<Ge, Sc>) {
+        self.records.flush();
+    }
+}
+
+impl<W, G, Sc> Inspector<G, Sc> for GenerationLogger<W>
+where
+    W: Write,
+    G: Display,
+    Sc: Ord + Clone + Display,
+{
+    fn on_generation(&mut self, generation: usize, population: &Population<G, Sc>) {
+        if self.records.error.is_none() {
+            self.records.error = self.log_generation(generation, population).err();
+        }
+    }
+
+    fn on_finish(&mut self, _report: &SearchReport<G, Sc>) {
+        self.records.flush();
+    }
+}
+
+/// A field value in a record. Numbers are written as they are, while text is
+/// quoted (if necessary) and escaped.
+#[derive(Debug)]
+enum Value {
+    Number(String),
+    Text(String),
+    Null,
+}
+
+#[derive(Debug)]
+struct RecordWriter<W> {
+    writer: W,
+    format: LogFormat,
+    wrote_header: bool,
+    error: Option<io::Error>,
+}
+
+impl<W> RecordWriter<W>
+where
+    W: Write,
+{
+    const fn new(writer: W, format: LogFormat) -> Self {
+        Self {
+            writer,
+            format,
+            wrote_header: false,
+            error: None,
+        }
+    }
+
+    /// Write one record. The field names have to be the same for every record
+    /// since they're only written (in the CSV header) once.
+    fn write(&mut self, fields: &[(&str, Value)]) -> io::Result<()> {
+        let line = match self.format {
+            LogFormat::Csv => {
+                if !self.wrote_header {
+                    let names = fields.iter().map(|(name, _)| csv_escape(name));
+                    writeln!(self.writer, "{}", names.collect::<Vec<_>>().join(","))?;
+                    self.wrote_header = true;
+                }
+                fields
+                    .iter()
+                    .map(|(_, value)| match value {
+                        Value::Number(number) => number.clone(),
+                        Value::Text(text) => csv_escape(text),
+                        Value::Null => String::new(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(",")
+            }
+            LogFormat::JsonLines => {
+                let members = fields.iter().map(|(name, value)| {
+                    let value = match value {
+                        Value::Number(number) => number.clone(),
+                        Value::Text(text) => json_string(text),
+                        Value::Null => "null".to_string(),
+                    };
+                    format!("{}:{value}", json_string(name))
+                });
+                format!("{{{}}}", members.collect::<Vec<_>>().join(","))
+            }
+        };
+        writeln!(self.writer, "{line}")
+    }
+
+    /// Flush the writer at the end of a search, keeping the error (if there
+    /// isn't one already) since inspectors can't return errors.
+    fn flush(&mut self) {
+        if self.error.is_none() {
+            self.error = self.writer.flush().err();
+        }
+    }
+}
+
+/// Quote `text` if it contains anything that has special meaning in CSV,
+/// doubling any quotes inside it.
+fn csv_escape(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// `text` as a (quoted) JSON string.
+fn json_string(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len() + 2);
+    escaped.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use ec_core::{
+        individual::scorer::FnScorer, operator::selector::tournament::Tournament,
+        test_results::Error,
+    };
+    use rand::distr::Uniform;
+
+    use super::*;
+    use crate::{
+        ec_run::{Run, Variation},
+        test_support::Step,
+    };
+
+    #[test]
+    fn escaping() {
+        assert_eq!(csv_escape("plain"), "plain");
+        assert_eq!(csv_escape("a,b"), "\"a,b\"");
+        assert_eq!(csv_escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(json_string("a \"b\"\\\n\u{1}"), r#""a \"b\"\\\n\u0001""#);
+    }
+
+    #[test]
+    fn generation_records_as_json_lines() {
+        let population = vec![
+            EcIndividual::new("(exec_dup \"x\")", 3),
+            EcIndividual::new("()", 7),
+            EcIndividual::new("(,)", 5),
+        ];
+        let mut logger = GenerationLogger::new(Vec::new(), LogFormat::JsonLines);
+        logger.log_generation(0, &population).unwrap();
+        logger.log_generation::<&str, i32>(1, &[]).unwrap();
+        let log = String::from_utf8(logger.into_inner()).unwrap();
+        let lines = log.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with(r#"{"generation":0,"elapsed_seconds":"#));
+        assert!(lines[0].ends_with(
+            r#""population_size":3,"best_score":"7","median_score":"5","worst_score":"3","best_genome":"()"}"#
+        ));
+        assert!(lines[1].ends_with(
+            r#""best_score":null,"median_score":null,"worst_score":null,"best_genome":null}"#
+        ));
+    }
+
+    /// Accepts everything that's written, but can't be flushed.
+    struct FailsToFlush;
+
+    impl Write for FailsToFlush {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Err(io::Error::other("Can't flush"))
+        }
+    }
+
+    fn run<W: Write>(
+        logger: &mut ImprovementLogger<W, Error<u32>>,
+    ) -> SearchReport<i32, Error<u32>> {
+        Run::builder()
+            .genome_maker(Uniform::new(-1_000, 1_000).unwrap())
+            .population_size(20)
+            .max_generations(10)
+            .seed(3)
+            .scorer(FnScorer(|value: &i32| Error(value.abs_diff(589))))
+            .selector(Tournament::of_size::<2>())
+            .variation(Variation::mutation(Step))
+            .inspector(logger)
+            .build()
+            .execute()
+            .unwrap()
+            .search
+    }
+
+    #[test]
+    fn improvements_are_logged_from_new_bests() {
+        // A `Run` never calls `on_samples`, so this only works because the
+        // logger records each new best.
+        let mut logger = ImprovementLogger::new(Vec::new(), LogFormat::Csv);
+        let report = run(&mut logger);
+        assert!(logger.error().is_none());
+        let best = report.best.unwrap();
+
+        let log = String::from_utf8(logger.into_inner()).unwrap();
+        let last = log.lines().last().unwrap().split(',').collect::<Vec<_>>();
+        assert_eq!(last[0], best.sample_number.to_string());
+        assert_eq!(last[2], best.score.to_string());
+        assert_eq!(last[3], best.genome.to_string());
+    }
+
+    #[test]
+    fn flush_errors_are_kept() {
+        let mut logger = ImprovementLogger::new(FailsToFlush, LogFormat::JsonLines);
+        run(&mut logger);
+        assert_eq!(logger.error().unwrap().to_string(), "Can't flush");
+    }
+}
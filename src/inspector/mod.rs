@@ -1,3 +1,11 @@
 mod best;
+mod channel;
+mod hooks;
+mod log;
+mod statistics;
 
 pub use best::*;
+pub use channel::*;
+pub use hooks::*;
+pub use log::*;
+pub use statistics::*;
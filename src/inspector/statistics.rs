@@ -0,0 +1,239 @@
+use std::{
+    collections::HashSet,
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
+use ec_core::individual::ec::EcIndividual;
+
+use super::Inspector;
+use crate::ec_run::Population;
+
+/// The worst, best, and quartile scores in a population. The quartiles are
+/// actual scores from the population (the nearest rank), since we can't
+/// generally average scores.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoreQuantiles<Sc> {
+    pub worst: Sc,
+    pub lower_quartile: Sc,
+    pub median: Sc,
+    pub upper_quartile: Sc,
+    pub best: Sc,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LengthStats {
+    pub min: usize,
+    pub mean: f64,
+    pub max: usize,
+}
+
+/// A summary of one generation of a run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerationStats<Sc> {
+    pub generation: usize,
+    pub population_size: usize,
+    /// `None` if the population was empty.
+    pub scores: Option<ScoreQuantiles<Sc>>,
+    /// `None` if the collector doesn't know how to measure genome lengths,
+    /// or the population was empty.
+    pub genome_length: Option<LengthStats>,
+    pub unique_genomes: usize,
+    /// The time since the collector was created.
+    pub elapsed: Duration,
+}
+
+/// Collects a [`GenerationStats`] summary of every generation it sees, for
+/// printing as the run goes or for analysis (or plotting) afterwards.
+///
+/// # Examples
+///
+/// Use it as (part of) a `Run` inspector, or call [`Statistics::record`] from
+/// your own inspector, and then get the number of unique genomes in each
+/// generation at the end.
+///
+/// ```
+/// # use course_helpers::inspector::Statistics;
+/// # use ec_core::{individual::ec::EcIndividual, test_results::Score};
+/// #
+/// let mut statistics = Statistics::with_genome_length(|genome: &Vec<bool>| genome.len());
+/// let mut inspector = |generation, population: &Vec<EcIndividual<Vec<bool>, Score<i32>>>| {
+///     statistics.record(generation, population);
+/// };
+///
+/// inspector(0, &vec![EcIndividual::new(vec![true], Score(1)), EcIndividual::new(vec![], Score(0))]);
+/// inspector(1, &vec![EcIndividual::new(vec![true], Score(1)), EcIndividual::new(vec![true], Score(1))]);
+///
+/// assert_eq!(statistics.series(|stats| stats.unique_genomes), [(0, 2), (1, 1)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Statistics<G, Sc> {
+    start: Instant,
+    genome_length: Option<fn(&G) -> usize>,
+    history: Vec<GenerationStats<Sc>>,
+}
+
+impl<G, Sc> Default for Statistics<G, Sc> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<G, Sc> Statistics<G, Sc> {
+    /// A collector that doesn't track genome lengths.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            genome_length: None,
+            history: Vec::new(),
+        }
+    }
+
+    /// A collector that also tracks genome lengths, as measured by
+    /// `genome_length`, e.g., `|plushy: &Plushy| plushy.get_genes().len()`.
+    #[must_use]
+    pub fn with_genome_length(genome_length: fn(&G) -> usize) -> Self {
+        Self {
+            genome_length: Some(genome_length),
+            ..Self::new()
+        }
+    }
+
+    /// Summarize `population` as generation number `generation`, and add the
+    /// summary to the history.
+    pub fn record(
+        &mut self,
+        generation: usize,
+        population: &[EcIndividual<G, Sc>],
+    ) -> &GenerationStats<Sc>
+    where
+        G: Hash + Eq,
+        Sc: Ord + Clone,
+    {
+        let stats = GenerationStats {
+            generation,
+            population_size: population.len(),
+            scores: score_quantiles(population),
+            genome_length: self
+                .genome_length
+                .and_then(|genome_length| length_stats(population, genome_length)),
+            unique_genomes: population
+                .iter()
+                .map(|individual| &individual.genome)
+                .collect::<HashSet<_>>()
+                .len(),
+            elapsed: self.start.elapsed(),
+        };
+        self.history.push(stats);
+        &self.history[self.history.len() - 1]
+    }
+
+    /// The summaries of every generation recorded so far, in order.
+    #[must_use]
+    pub fn history(&self) -> &[GenerationStats<Sc>] {
+        &self.history
+    }
+
+    #[must_use]
+    pub fn into_history(self) -> Vec<GenerationStats<Sc>> {
+        self.history
+    }
+
+    /// One value from each generation's summary, paired with the generation
+    /// number, e.g., `statistics.series(|stats| stats.scores.as_ref().map(|s| s.median.clone()))`.
+    pub fn series<T>(&self, value: impl Fn(&GenerationStats<Sc>) -> T) -> Vec<(usize, T)> {
+        self.history
+            .iter()
+            .map(|stats| (stats.generation, value(stats)))
+            .collect()
+    }
+}
+
+impl<G, Sc> Inspector<G, Sc> for Statistics<G, Sc>
+where
+    G: Hash + Eq,
+    Sc: Ord + Clone,
+{
+    fn on_generation(&mut self, generation: usize, population: &Population<G, Sc>) {
+        self.record(generation, population);
+    }
+}
+
+pub(crate) fn score_quantiles<G, Sc>(
+    population: &[EcIndividual<G, Sc>],
+) -> Option<ScoreQuantiles<Sc>>
+where
+    Sc: Ord + Clone,
+{
+    let mut scores = population
+        .iter()
+        .map(|individual| &individual.test_results)
+        .collect::<Vec<_>>();
+    scores.sort_unstable();
+    let last = scores.len().checked_sub(1)?;
+    let quartile = |quarters: usize| scores[(last * quarters + 2) / 4].clone();
+    Some(ScoreQuantiles {
+        worst: quartile(0),
+        lower_quartile: quartile(1),
+        median: quartile(2),
+        upper_quartile: quartile(3),
+        best: quartile(4),
+    })
+}
+
+#[expect(
+    clippy::cast_precision_loss,
+    reason = "Genome lengths are small enough to be represented exactly in an `f64`."
+)]
+fn length_stats<G, Sc>(
+    population: &[EcIndividual<G, Sc>],
+    genome_length: fn(&G) -> usize,
+) -> Option<LengthStats> {
+    let lengths = population
+        .iter()
+        .map(|individual| genome_length(&individual.genome))
+        .collect::<Vec<_>>();
+    Some(LengthStats {
+        min: *lengths.iter().min()?,
+        mean: lengths.iter().sum::<usize>() as f64 / lengths.len() as f64,
+        max: *lengths.iter().max()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quartiles_and_lengths() {
+        let population = [5, 1, 4, 2, 3]
+            .into_iter()
+            .map(|score| EcIndividual::new("x".repeat(score), score))
+            .collect::<Vec<_>>();
+        let mut statistics = Statistics::with_genome_length(String::len);
+        let stats = statistics.record(3, &population).clone();
+
+        assert_eq!(
+            stats.scores,
+            Some(ScoreQuantiles {
+                worst: 1,
+                lower_quartile: 2,
+                median: 3,
+                upper_quartile: 4,
+                best: 5,
+            })
+        );
+        assert_eq!(
+            stats.genome_length,
+            Some(LengthStats {
+                min: 1,
+                mean: 3.0,
+                max: 5
+            })
+        );
+        assert_eq!(stats.unique_genomes, 5);
+        assert_eq!(statistics.record(4, &[]).scores, None);
+        assert_eq!(statistics.history().len(), 2);
+    }
+}
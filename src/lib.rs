@@ -1,5 +1,14 @@
 pub mod ec_run;
+pub mod evolution_strategy;
 pub mod hill_climber;
 pub mod inspector;
+pub mod numeric_score;
 pub mod random_search;
+pub mod report;
+mod seeding;
 pub mod simplifier;
+pub mod simulated_annealing;
+pub mod tabu_search;
+pub mod termination;
+#[cfg(test)]
+pub(crate) mod test_support;
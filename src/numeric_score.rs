@@ -0,0 +1,32 @@
+use ec_core::test_results::{Error, Score, TestResults};
+use num_traits::ToPrimitive;
+
+/// A numeric view of a score, for search algorithms (like simulated annealing)
+/// that need to know how _much_ better one score is than another, and not just
+/// which one is better.
+///
+/// As with the scores' ordering, higher values are better, so an [`Error`] is
+/// the negation of the error.
+pub trait NumericScore {
+    /// This score as an `f64`, or NaN if it can't be represented as one.
+    fn to_f64(&self) -> f64;
+}
+
+impl<T: ToPrimitive> NumericScore for Score<T> {
+    fn to_f64(&self) -> f64 {
+        self.0.to_f64().unwrap_or(f64::NAN)
+    }
+}
+
+impl<T: ToPrimitive> NumericScore for Error<T> {
+    fn to_f64(&self) -> f64 {
+        -self.0.to_f64().unwrap_or(f64::NAN)
+    }
+}
+
+/// The total result, since that's what `TestResults` are ordered by first.
+impl<R: NumericScore> NumericScore for TestResults<R> {
+    fn to_f64(&self) -> f64 {
+        self.total_result.to_f64()
+    }
+}
@@ -1,10 +1,87 @@
-use std::{marker::PhantomData, sync::Mutex};
+use std::{
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
 
 use bon::Builder;
 use ec_core::individual::scorer::Scorer;
-use rand::{prelude::Distribution, rng};
+use rand::{prelude::Distribution, rngs::StdRng};
 use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
 
+use crate::{
+    inspector::Inspector,
+    report::{BestFound, SearchReport},
+    seeding::{seed_or_random, stream_rng},
+    termination::{ProgressTracker, Termination, TerminationReason},
+};
+
+// A *little* searching on a simple problem suggests that something like
+// 1,000 samples per chunk is a good balance between the overhead of locking
+// and the benefit of parallelism. This is a good starting point, but you
+// may want to experiment with different chunk sizes.
+//
+// Each chunk also gets its own RNG (derived from the search's seed and the
+// chunk number), so the sequential search uses the same chunks to generate
+// exactly the same samples as the parallel search.
+const DEFAULT_CHUNK_SIZE: usize = 1_000;
+
+/// How the parallel [`RandomSearch`] collects the results from its threads.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Aggregation {
+    /// Pass every chunk of samples to the inspector as it's finished, locking
+    /// the inspector once per chunk. The termination criteria are checked
+    /// after each chunk (and the score-based ones after every sample).
+    #[default]
+    EveryChunk,
+    /// Have each thread keep a summary of its chunks (the best sample, and how
+    /// many samples there were) without locking anything, and merge the
+    /// summaries. This is much faster with cheap scorers, but the inspector
+    /// only sees the best sample from each round of `chunk_size` samples per
+    /// thread, and the termination criteria that don't depend on the score
+    /// are only checked between rounds. Each chunk is still a step, though,
+    /// and rounds end early if a step-based criterion might be met, so
+    /// [`Termination::MaxSteps`] and [`Termination::NoImprovement`] stop the
+    /// search at the same point whatever the number of threads.
+    Reduce,
+}
+
+/// What we know about some samples in [`Aggregation::Reduce`] mode.
+#[derive(Debug)]
+struct Summary<Ge, Sc> {
+    best: Option<(usize, Ge, Sc)>,
+    num_samples: usize,
+}
+
+impl<Ge, Sc: PartialOrd> Summary<Ge, Sc> {
+    const fn empty() -> Self {
+        Self {
+            best: None,
+            num_samples: 0,
+        }
+    }
+
+    /// Combine two summaries. Like [`ProgressTracker::record`], ties go to the
+    /// sample with the lower sample number, so the result doesn't depend on
+    /// the order summaries are merged in.
+    fn merge(self, other: Self) -> Self {
+        let best = match (self.best, other.best) {
+            (Some(first), Some(second)) => {
+                let second_is_better =
+                    second.2 > first.2 || (second.2 == first.2 && second.0 < first.0);
+                Some(if second_is_better { second } else { first })
+            }
+            (first, second) => first.or(second),
+        };
+        Self {
+            best,
+            num_samples: self.num_samples + other.num_samples,
+        }
+    }
+}
+
 #[derive(Debug, Builder)]
 pub struct RandomSearch<Ge, GM, Sc, Scr, Ins>
 // You typically wouldn't put all these constraints on the struct itself, instead
@@ -14,10 +91,11 @@ pub struct RandomSearch<Ge, GM, Sc, Scr, Ins>
 where
     Ge: Clone + std::fmt::Debug + Sync + Send,
     GM: Distribution<Ge> + Sync + Send,
-    Sc: std::fmt::Debug + Sync + Send,
+    Sc: std::fmt::Debug + Clone + PartialOrd + Sync + Send,
     Scr: Scorer<Ge, Score = Sc> + Sync + Send,
-    // The number of this particular genome, the genome, and its score.
-    Ins: FnMut(&[(usize, Ge, Sc)]) + Sync + Send,
+    // The inspector is shown the (sample number, genome, score) solutions
+    // as they are evaluated.
+    Ins: Inspector<Ge, Sc> + Sync + Send,
 {
     // We need `PhantomData` because `RandomSearch` depends on the type `Ge` but doesn't
     // actually contain an instance of it. This is a way to tell Rust that `Ge`
@@ -33,6 +111,31 @@ where
     #[builder(default = true)]
     parallel_search: bool,
 
+    /// The number of samples in each chunk. In parallel mode each chunk is
+    /// evaluated by one thread and then passed to the inspector at once, so
+    /// bigger chunks mean less time waiting for the lock on the inspector.
+    /// Each chunk has its own RNG, so changing the chunk size changes the
+    /// samples. Chunks have at least one sample, whatever this is set to.
+    #[builder(default = DEFAULT_CHUNK_SIZE)]
+    chunk_size: usize,
+
+    /// How the parallel search collects the results from its threads. This
+    /// doesn't affect the sequential search.
+    #[builder(default)]
+    aggregation: Aggregation,
+
+    /// Additional criteria for stopping the search before `num_to_search`
+    /// samples have been evaluated. The score-based criteria
+    /// ([`Termination::TargetScore`] and [`Termination::BestSatisfies`]) are
+    /// checked after every sample, so every thread in a parallel search stops
+    /// promptly once any of them reaches the target.
+    #[builder(default)]
+    termination: Termination<Sc>,
+
+    /// The seed for the random number generators. If this isn't specified a
+    /// random seed is used, which is included in the returned report.
+    seed: Option<u64>,
+
     genome_maker: GM,
     scorer: Scr,
     inspector: Ins,
@@ -42,16 +145,48 @@ impl<Ge, GM, Sc, Scr, Ins> RandomSearch<Ge, GM, Sc, Scr, Ins>
 where
     Ge: Clone + std::fmt::Debug + Sync + Send,
     GM: Distribution<Ge> + Sync + Send,
-    Sc: std::fmt::Debug + Sync + Send,
+    Sc: std::fmt::Debug + Clone + PartialOrd + Sync + Send,
     Scr: Scorer<Ge, Score = Sc> + Sync + Send,
-    // The number of this particular genome, the genome, and its score.
-    Ins: FnMut(&[(usize, Ge, Sc)]) + Sync + Send,
+    // The inspector is shown the (sample number, genome, score) solutions
+    // as they are evaluated.
+    Ins: Inspector<Ge, Sc> + Sync + Send,
 {
-    pub fn search(&mut self) {
+    /// Search until `num_to_search` samples have been evaluated or the
+    /// `termination` criteria are met, returning a report with the best
+    /// genome found, the number of samples evaluated, and the reason the
+    /// search stopped.
+    ///
+    /// Given the same seed, the sequential and parallel searches generate the
+    /// same samples and find the same best genome (as long as they aren't
+    /// stopped early by the `termination` criteria).
+    pub fn search(&mut self) -> SearchReport<Ge, Sc> {
         if self.parallel_search {
-            self.search_parallel()
+            let seed = seed_or_random(self.seed);
+            self.inspector.on_start();
+            let report = match self.aggregation {
+                Aggregation::EveryChunk => self.search_parallel(seed),
+                Aggregation::Reduce => self.search_parallel_reduce(seed),
+            };
+            self.inspector.on_finish(&report);
+            report
         } else {
-            self.search_sequential()
+            let mut stepper = self.stepper();
+            while stepper.step() {}
+            stepper.finish()
+        }
+    }
+
+    /// Start a search that the caller runs one sample at a time. See
+    /// [`RandomSearchStepper`].
+    pub fn stepper(&mut self) -> RandomSearchStepper<'_, Ge, GM, Sc, Scr, Ins> {
+        let seed = seed_or_random(self.seed);
+        self.inspector.on_start();
+        RandomSearchStepper {
+            search: self,
+            tracker: ProgressTracker::new(seed),
+            rng: stream_rng(seed, 0),
+            sample_number: 0,
+            termination_reason: None,
         }
     }
 
@@ -62,40 +197,386 @@ where
     /// (e.g., a "best so far" field), the `inspector` is wrapped in a `Mutex` to
     /// ensure that only one thread can access it at a time. That creates a potential
     /// bottleneck, but it's a simple way to ensure that the `inspector` is thread-safe.
-    /// We break the search into chunks of 1,000 samples to reduce the number of times
-    /// the `Mutex` is locked and unlocked, reducing the contention.
-    fn search_parallel(&mut self) {
-        // A *little* searching on a simple problem suggests that something like
-        // 1,000 samples per chunk is a good balance between the overhead of locking
-        // and the benefit of parallelism. This is a good starting point, but you
-        // may want to experiment with different chunk sizes.
-        const CHUNK_SIZE: usize = 1_000;
-        let inspector = Mutex::new(&mut self.inspector);
+    /// We break the search into chunks of `chunk_size` samples to reduce the number of
+    /// times the `Mutex` is locked and unlocked, reducing the contention.
+    ///
+    /// The termination criteria are checked after each chunk, and the
+    /// score-based ones after each sample. Once they're met, any chunks that haven't
+    /// started yet are skipped, and any chunks that are still in progress stop
+    /// after their current sample. The samples they've already evaluated are
+    /// still recorded, so the report's `evaluations` is the number of samples
+    /// that were actually scored.
+    fn search_parallel(&mut self, seed: u64) -> SearchReport<Ge, Sc> {
+        let chunk_size = self.chunk_size.max(1);
+        let termination = &self.termination;
+        let state = Mutex::new((&mut self.inspector, ProgressTracker::new(seed), None));
+        let stopped = AtomicBool::new(false);
         (0..self.num_to_search)
             .into_par_iter()
-            .chunks(CHUNK_SIZE)
+            .chunks(chunk_size)
             .for_each(|chunk| {
+                let mut rng = stream_rng(seed, chunk[0] / chunk_size);
                 let solution_chunk = chunk
                     .into_iter()
-                    .map(|sample_number| {
+                    .map_while(|sample_number| {
+                        if stopped.load(Ordering::Relaxed) {
+                            return None;
+                        }
                         // Generate a random genome as a "solution"
-                        let sample = self.genome_maker.sample(&mut rng());
+                        let sample = self.genome_maker.sample(&mut rng);
                         // Score the solution
                         let score = self.scorer.score(&sample);
-                        (sample_number, sample, score)
+                        if termination.reached_by(&score) {
+                            stopped.store(true, Ordering::Relaxed);
+                        }
+                        Some((sample_number, sample, score))
                     })
                     .collect::<Vec<_>>();
-                (inspector.lock().unwrap())(&solution_chunk);
+                if solution_chunk.is_empty() {
+                    return;
+                }
+                let mut state = state.lock().unwrap();
+                let (inspector, tracker, termination_reason) = &mut *state;
+                tracker.finish_step();
+                for (sample_number, sample, score) in &solution_chunk {
+                    tracker.record_and_inspect(*sample_number, sample, score, inspector);
+                }
+                inspector.on_samples(&solution_chunk);
+                if termination_reason.is_none() {
+                    *termination_reason = tracker.check(termination);
+                    if termination_reason.is_some() {
+                        stopped.store(true, Ordering::Relaxed);
+                    }
+                }
             });
+        let (_, tracker, termination_reason) = state.into_inner().unwrap();
+        tracker.into_report(termination_reason.unwrap_or(TerminationReason::MaxEvaluations))
+    }
+
+    /// Search the given number of samples in parallel, in rounds of (at most)
+    /// one chunk of `chunk_size` samples per thread. Each chunk in a round is
+    /// summarized without any locking, and the summaries are merged with a
+    /// parallel `reduce`. Only the merged summary for each round is recorded
+    /// and shown to the inspector, and the termination criteria are checked
+    /// between rounds. The score-based criteria are checked after every
+    /// sample, though, and once any thread meets them the others stop after
+    /// their current sample.
+    fn search_parallel_reduce(&mut self, seed: u64) -> SearchReport<Ge, Sc> {
+        let chunk_size = self.chunk_size.max(1);
+        let num_threads = rayon::current_num_threads();
+        let termination = &self.termination;
+        let stopped = AtomicBool::new(false);
+        let mut tracker = ProgressTracker::new(seed);
+        let mut round_start = 0;
+        while round_start < self.num_to_search {
+            if let Some(reason) = tracker.check(termination) {
+                return tracker.into_report(reason);
+            }
+            // Each chunk is a step. A round has a chunk for each thread, but
+            // never more chunks than we can take before one of the step-based
+            // termination criteria might be met, so those criteria stop the
+            // search at the same step however many threads there are.
+            let num_chunks = termination
+                .steps_before_check(&tracker.progress())
+                .map_or(num_threads, |steps| steps.clamp(1, num_threads));
+            let round_end = self
+                .num_to_search
+                .min(round_start.saturating_add(chunk_size.saturating_mul(num_chunks)));
+            let summary = (round_start..round_end)
+                .into_par_iter()
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    let mut rng = stream_rng(seed, chunk[0] / chunk_size);
+                    chunk
+                        .into_iter()
+                        .map_while(|sample_number| {
+                            if stopped.load(Ordering::Relaxed) {
+                                return None;
+                            }
+                            let sample = self.genome_maker.sample(&mut rng);
+                            let score = self.scorer.score(&sample);
+                            if termination.reached_by(&score) {
+                                stopped.store(true, Ordering::Relaxed);
+                            }
+                            Some(Summary {
+                                best: Some((sample_number, sample, score)),
+                                num_samples: 1,
+                            })
+                        })
+                        .fold(Summary::empty(), Summary::merge)
+                })
+                .reduce(Summary::empty, Summary::merge);
+
+            let first_step = tracker.steps() + 1;
+            tracker.advance_to(tracker.steps() + (round_end - round_start).div_ceil(chunk_size));
+            let best_step = summary
+                .best
+                .as_ref()
+                .map_or(first_step, |(sample_number, _, _)| {
+                    first_step + (sample_number - round_start) / chunk_size
+                });
+            tracker.record_summarized(
+                summary.num_samples,
+                summary.best.as_ref(),
+                best_step,
+                &mut self.inspector,
+            );
+            if let Some(best) = summary.best {
+                self.inspector.on_samples(&[best]);
+            }
+            round_start = round_end;
+        }
+        let reason = tracker
+            .check(termination)
+            .unwrap_or(TerminationReason::MaxEvaluations);
+        tracker.into_report(reason)
+    }
+}
+
+/// A [`RandomSearch`] that the caller runs one sample at a time, e.g., to look
+/// at the best genome so far between samples, or to drive the search from a
+/// test or a REPL. Make one with [`RandomSearch::stepper`].
+///
+/// The stepper always evaluates the samples one at a time, in order, even if
+/// `parallel_search` is set. Given the same seed, it generates the same
+/// samples as [`RandomSearch::search`].
+#[derive(Debug)]
+pub struct RandomSearchStepper<'a, Ge, GM, Sc, Scr, Ins>
+where
+    Ge: Clone + std::fmt::Debug + Sync + Send,
+    GM: Distribution<Ge> + Sync + Send,
+    Sc: std::fmt::Debug + Clone + PartialOrd + Sync + Send,
+    Scr: Scorer<Ge, Score = Sc> + Sync + Send,
+    Ins: Inspector<Ge, Sc> + Sync + Send,
+{
+    search: &'a mut RandomSearch<Ge, GM, Sc, Scr, Ins>,
+    tracker: ProgressTracker<Ge, Sc>,
+    // The RNG for the chunk that `sample_number` is in.
+    rng: StdRng,
+    // The sample number of the next genome to evaluate.
+    sample_number: usize,
+    termination_reason: Option<TerminationReason>,
+}
+
+impl<Ge, GM, Sc, Scr, Ins> RandomSearchStepper<'_, Ge, GM, Sc, Scr, Ins>
+where
+    Ge: Clone + std::fmt::Debug + Sync + Send,
+    GM: Distribution<Ge> + Sync + Send,
+    Sc: std::fmt::Debug + Clone + PartialOrd + Sync + Send,
+    Scr: Scorer<Ge, Score = Sc> + Sync + Send,
+    Ins: Inspector<Ge, Sc> + Sync + Send,
+{
+    /// Generate and score one sample. Returns `false`, without doing
+    /// anything, if the search is over.
+    pub fn step(&mut self) -> bool {
+        if self.termination_reason.is_none() {
+            self.termination_reason = self.check();
+        }
+        if self.termination_reason.is_some() {
+            return false;
+        }
+        let sample_number = self.sample_number;
+        let chunk_size = self.search.chunk_size.max(1);
+        if sample_number.is_multiple_of(chunk_size) {
+            self.rng = stream_rng(self.tracker.seed(), sample_number / chunk_size);
+        }
+        let search = &mut *self.search;
+        // Generate a random genome as a "solution"
+        let sample = search.genome_maker.sample(&mut self.rng);
+        // Score the solution
+        let score = search.scorer.score(&sample);
+        self.tracker.finish_step();
+        self.tracker
+            .record_and_inspect(sample_number, &sample, &score, &mut search.inspector);
+        search
+            .inspector
+            .on_samples(&[(sample_number, sample, score)]);
+        self.sample_number += 1;
+        true
+    }
+
+    /// Why the search would stop now, if it would. Since this is checked
+    /// before every sample, the search stops right after the first sample
+    /// that meets the score-based termination criteria.
+    fn check(&self) -> Option<TerminationReason> {
+        self.tracker.check(&self.search.termination).or_else(|| {
+            (self.sample_number >= self.search.num_to_search)
+                .then_some(TerminationReason::MaxEvaluations)
+        })
+    }
+
+    /// The best genome found so far.
+    pub const fn best(&self) -> Option<&BestFound<Ge, Sc>> {
+        self.tracker.best()
+    }
+
+    /// The number of samples evaluated so far.
+    pub const fn evaluations(&self) -> usize {
+        self.tracker.evaluations()
+    }
+
+    /// Why the search stopped, once [`RandomSearchStepper::step`] has returned
+    /// `false`.
+    pub const fn termination_reason(&self) -> Option<&TerminationReason> {
+        self.termination_reason.as_ref()
+    }
+
+    /// End the search, returning the same report as [`RandomSearch::search`].
+    /// If the search would have kept going, the termination reason is
+    /// [`TerminationReason::Stopped`].
+    pub fn finish(self) -> SearchReport<Ge, Sc> {
+        let reason = self
+            .termination_reason
+            .clone()
+            .or_else(|| self.check())
+            .unwrap_or(TerminationReason::Stopped);
+        let report = self.tracker.into_report(reason);
+        self.search.inspector.on_finish(&report);
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ec_core::{individual::scorer::FnScorer, test_results::Error};
+    use rand::distr::Uniform;
+
+    use super::*;
+
+    fn seeded_search(
+        parallel_search: bool,
+        chunk_size: usize,
+        aggregation: Aggregation,
+    ) -> SearchReport<i32, Error<u32>> {
+        RandomSearch::builder()
+            .num_to_search(10_500)
+            .parallel_search(parallel_search)
+            .chunk_size(chunk_size)
+            .aggregation(aggregation)
+            .seed(42)
+            .genome_maker(Uniform::new(-1_000_000, 1_000_000).unwrap())
+            .scorer(FnScorer(|value: &i32| Error(value.abs_diff(589))))
+            .inspector(())
+            .build()
+            .search()
+    }
+
+    #[test]
+    fn seeded_sequential_and_parallel_searches_agree() {
+        let sequential = seeded_search(false, 1_000, Aggregation::EveryChunk);
+        let parallel = seeded_search(true, 1_000, Aggregation::EveryChunk);
+        assert_eq!(sequential.seed, 42);
+        assert_eq!(sequential.evaluations, parallel.evaluations);
+        // Steps are chunks in the parallel search, so we don't compare `step`.
+        assert_eq!(best(sequential), best(parallel));
+    }
+
+    #[test]
+    fn reducing_parallel_results_finds_the_same_best() {
+        let sequential = seeded_search(false, 300, Aggregation::EveryChunk);
+        let reduced = seeded_search(true, 300, Aggregation::Reduce);
+        assert_eq!(reduced.evaluations, 10_500);
+        assert_eq!(best(sequential), best(reduced));
+    }
+
+    #[test]
+    fn stopping_at_the_target_score() {
+        let near_the_target: fn(&Error<u32>) -> bool = |score| score.0 <= 1_000;
+        for termination in [
+            Termination::TargetScore(Error(1_000)),
+            Termination::BestSatisfies(near_the_target),
+        ] {
+            for (parallel_search, aggregation) in [
+                (false, Aggregation::EveryChunk),
+                (true, Aggregation::EveryChunk),
+                (true, Aggregation::Reduce),
+            ] {
+                let report = RandomSearch::builder()
+                    .num_to_search(1_000_000)
+                    .parallel_search(parallel_search)
+                    .chunk_size(100_000)
+                    .aggregation(aggregation)
+                    .termination(termination.clone())
+                    .seed(42)
+                    .genome_maker(Uniform::new(-1_000_000, 1_000_000).unwrap())
+                    .scorer(FnScorer(|value: &i32| Error(value.abs_diff(589))))
+                    .inspector(())
+                    .build()
+                    .search();
+                assert_eq!(report.termination_reason, TerminationReason::TargetScore);
+                assert!(report.best.unwrap().score >= Error(1_000));
+                // We expect to hit the target after about 1,000 samples, well
+                // before the end of the first chunk.
+                assert!(report.evaluations < 100_000, "{}", report.evaluations);
+            }
+        }
+    }
+
+    #[test]
+    fn reduced_searches_stop_at_the_same_step_with_any_number_of_threads() {
+        let search_with_threads = |num_threads, termination: Termination<Error<u32>>| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(num_threads)
+                .build()
+                .unwrap()
+                .install(|| {
+                    RandomSearch::builder()
+                        .num_to_search(100_000)
+                        .chunk_size(100)
+                        .aggregation(Aggregation::Reduce)
+                        .termination(termination)
+                        .seed(42)
+                        .genome_maker(Uniform::new(-1_000_000, 1_000_000).unwrap())
+                        .scorer(FnScorer(|value: &i32| Error(value.abs_diff(589))))
+                        .inspector(())
+                        .build()
+                        .search()
+                })
+        };
+
+        let one_thread = search_with_threads(1, Termination::MaxSteps(3));
+        let four_threads = search_with_threads(4, Termination::MaxSteps(3));
+        assert_eq!(one_thread.termination_reason, TerminationReason::MaxSteps);
+        assert_eq!((one_thread.steps, one_thread.evaluations), (3, 300));
+        assert_eq!((four_threads.steps, four_threads.evaluations), (3, 300));
+        assert_eq!(best(one_thread), best(four_threads));
+
+        let one_thread = search_with_threads(1, Termination::NoImprovement(5));
+        let four_threads = search_with_threads(4, Termination::NoImprovement(5));
+        assert_eq!(
+            one_thread.termination_reason,
+            TerminationReason::NoImprovement
+        );
+        assert_eq!(one_thread.steps, four_threads.steps);
+        assert_eq!(one_thread.evaluations, four_threads.evaluations);
+    }
+
+    #[test]
+    fn a_single_chunk_doesnt_overflow() {
+        let report = seeded_search(true, usize::MAX, Aggregation::Reduce);
+        assert_eq!((report.steps, report.evaluations), (1, 10_500));
+    }
+
+    fn best(report: SearchReport<i32, Error<u32>>) -> Option<(usize, i32, Error<u32>)> {
+        report
+            .best
+            .map(|best| (best.sample_number, best.genome, best.score))
     }
 
-    fn search_sequential(&mut self) {
-        for sample_number in 0..self.num_to_search {
-            // Generate a random genome as a "solution"
-            let sample = self.genome_maker.sample(&mut rng());
-            // Score the solution
-            let score = self.scorer.score(&sample);
-            (self.inspector)(&[(sample_number, sample.clone(), score)]);
+    #[test]
+    fn stopping_a_stepper_early() {
+        let mut random_search = RandomSearch::builder()
+            .seed(42)
+            .genome_maker(Uniform::new(-1_000_000, 1_000_000).unwrap())
+            .scorer(FnScorer(|value: &i32| Error(value.abs_diff(589))))
+            .inspector(())
+            .build();
+        let mut stepper = random_search.stepper();
+        for _ in 0..10 {
+            assert!(stepper.step());
         }
+        let report = stepper.finish();
+        assert_eq!(report.evaluations, 10);
+        assert_eq!(report.termination_reason, TerminationReason::Stopped);
     }
 }
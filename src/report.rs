@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use crate::termination::TerminationReason;
+
+/// The best genome found during a search, and when it was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BestFound<Ge, Sc> {
+    pub genome: Ge,
+    pub score: Sc,
+    /// The sample number of this genome, i.e., how many evaluations had been
+    /// done before it was evaluated.
+    pub sample_number: usize,
+    /// The step (e.g., generation) in which this genome was found.
+    pub step: usize,
+}
+
+/// A summary of a completed search, returned by each of the search algorithms.
+///
+/// # Examples
+///
+/// ```
+/// # use course_helpers::{random_search::RandomSearch, report::SearchReport};
+/// # use ec_core::{individual::scorer::FnScorer, test_results::Error};
+/// # use rand::distr::Uniform;
+/// #
+/// let mut random_search = RandomSearch::builder()
+///     .num_to_search(1_000)
+///     .genome_maker(Uniform::new(0, 100).unwrap())
+///     .scorer(FnScorer(|value: &i32| Error(value.abs_diff(42))))
+///     .inspector(())
+///     .build();
+///
+/// let report: SearchReport<i32, Error<u32>> = random_search.search();
+/// assert_eq!(report.evaluations, 1_000);
+/// assert!(report.best.is_some());
+/// ```
+#[derive(Debug, Clone)]
+pub struct SearchReport<Ge, Sc> {
+    /// The best genome seen at any point in the search, or `None` if the
+    /// search didn't evaluate anything.
+    pub best: Option<BestFound<Ge, Sc>>,
+    /// The total number of genomes evaluated.
+    pub evaluations: usize,
+    /// The number of steps (e.g., generations) taken.
+    pub steps: usize,
+    pub elapsed: Duration,
+    pub termination_reason: TerminationReason,
+    /// The seed the search used. Passing this to the search's builder will
+    /// repeat the search exactly.
+    pub seed: u64,
+}
+
+/// A summary of one climb in a `HillClimber` search that restarts, passed to
+/// inspectors as a [`SearchEvent::ClimbFinished`](crate::inspector::SearchEvent::ClimbFinished).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClimbReport<Ge, Sc> {
+    /// The number of this climb, counting from zero.
+    pub climb: usize,
+    /// The sample number of the genome this climb started from.
+    pub first_sample: usize,
+    /// The number of genomes evaluated in this climb, including its start.
+    pub evaluations: usize,
+    /// The best genome found in this climb. This is only the best of the whole
+    /// search if it's also the search's [`SearchReport::best`].
+    pub best: BestFound<Ge, Sc>,
+}
@@ -0,0 +1,31 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// The seed to use for a search: the one the user asked for, or a random
+/// one if they didn't specify one. Either way the seed ends up in the
+/// [`SearchReport`](crate::report::SearchReport) so the search can be repeated.
+pub(crate) fn seed_or_random(seed: Option<u64>) -> u64 {
+    seed.unwrap_or_else(|| rand::rng().random())
+}
+
+/// Derive a new seed for the given "stream" (e.g., a chunk of samples, a
+/// generation, or a single child) from `seed`.
+///
+/// This is the `SplitMix64` finalizer, which scrambles the bits well enough
+/// that nearby seeds and streams give unrelated results.
+pub(crate) const fn derive_seed(seed: u64, stream: u64) -> u64 {
+    const fn mix(value: u64) -> u64 {
+        let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+    mix(seed ^ mix(stream))
+}
+
+/// An RNG for the given stream of a search with the given seed.
+///
+/// The same seed and stream always give the same RNG, no matter which thread
+/// asks for it, which is what makes parallel searches reproducible.
+pub(crate) fn stream_rng(seed: u64, stream: usize) -> StdRng {
+    StdRng::seed_from_u64(derive_seed(seed, stream as u64))
+}
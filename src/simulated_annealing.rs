@@ -0,0 +1,230 @@
+use core::slice;
+use std::{f64::consts::E, marker::PhantomData};
+
+use bon::Builder;
+use ec_core::{individual::scorer::Scorer, operator::mutator::Mutator};
+use rand::{prelude::Distribution, Rng};
+
+use crate::{
+    hill_climber::{finish_search, start_search},
+    inspector::Inspector,
+    numeric_score::NumericScore,
+    report::SearchReport,
+    seeding::stream_rng,
+    termination::Termination,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum SimulatedAnnealingError<MutationError> {
+    #[error(transparent)]
+    Mutation(#[from] MutationError),
+}
+
+/// How the temperature of a [`SimulatedAnnealing`] search drops as the search
+/// goes on. The temperature is on the same scale as the scores: a child that's
+/// worse than the current solution by `delta` is accepted with probability
+/// `exp(-delta / temperature)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CoolingSchedule {
+    /// Drop the temperature by the same amount each step, from `initial` down
+    /// to zero at the end of the search.
+    Linear { initial: f64 },
+    /// Multiply the temperature by `alpha` (typically just below 1) each step.
+    Geometric { initial: f64, alpha: f64 },
+    /// Divide `initial` by `ln(step + e)`, which cools quickly at first and
+    /// then very slowly.
+    Logarithmic { initial: f64 },
+}
+
+impl CoolingSchedule {
+    /// The temperature at the given step of a search that takes `num_steps`
+    /// steps in all.
+    #[must_use]
+    pub fn temperature(self, step: usize, num_steps: usize) -> f64 {
+        // These are only approximate for huge step counts, which is fine for
+        // a temperature.
+        #[expect(
+            clippy::cast_precision_loss,
+            reason = "Step counts are far below 2^52 in practice."
+        )]
+        let (step, num_steps) = (step as f64, num_steps as f64);
+        match self {
+            Self::Linear { initial } => {
+                if num_steps == 0.0 {
+                    initial
+                } else {
+                    initial * (1.0 - step / num_steps).max(0.0)
+                }
+            }
+            Self::Geometric { initial, alpha } => initial * alpha.powf(step),
+            Self::Logarithmic { initial } => initial / (step + E).ln(),
+        }
+    }
+}
+
+/// Should a search at the given temperature move from a solution with score
+/// `current` to one with score `candidate`? Children that are at least as
+/// good are always accepted.
+fn accept(current: f64, candidate: f64, temperature: f64, rng: &mut impl Rng) -> bool {
+    let delta = candidate - current;
+    if delta >= 0.0 {
+        return true;
+    }
+    temperature > 0.0 && rng.random::<f64>() < (delta / temperature).exp()
+}
+
+#[derive(Debug, Builder)]
+pub struct SimulatedAnnealing<Ge, GM, Mut, Sc, Scr, Ins>
+// You typically wouldn't put all these constraints on the struct itself, instead
+// you'd put them on the `impl` block for the struct. But I'm doing it here to
+// make the constraints more visible and (hopefully) make some of the error
+// messages more helpful for people new to Rust.
+where
+    Ge: Clone,
+    GM: Distribution<Ge>,
+    Mut: Mutator<Ge>,
+    // The acceptance rule needs to know how much worse a child is, so the
+    // scores have to have a numeric value.
+    Sc: NumericScore + PartialOrd,
+    Scr: Scorer<Ge, Score = Sc>,
+    // The inspector is shown each new current solution as a
+    // (sample number, genome, score) triple.
+    Ins: Inspector<Ge, Sc>,
+{
+    // See `HillClimber` for why we need this.
+    #[builder(field)]
+    _p: PhantomData<Ge>,
+
+    #[builder(default = 1_000)]
+    num_to_search: usize,
+
+    /// How the temperature drops over the course of the search. The search
+    /// takes one step (and makes one child) for each genome after the first.
+    cooling_schedule: CoolingSchedule,
+
+    /// Additional criteria for stopping the search before `num_to_search`
+    /// genomes have been evaluated.
+    #[builder(default)]
+    termination: Termination<Sc>,
+
+    /// The seed for the random number generator. If this isn't specified a
+    /// random seed is used, which is included in the returned report.
+    seed: Option<u64>,
+
+    genome_maker: GM,
+    mutator: Mut,
+    scorer: Scr,
+    inspector: Ins,
+}
+
+impl<Ge, GM, Mut, Sc, Scr, Ins> SimulatedAnnealing<Ge, GM, Mut, Sc, Scr, Ins>
+where
+    Ge: Clone,
+    GM: Distribution<Ge>,
+    Mut: Mutator<Ge>,
+    Sc: NumericScore + PartialOrd + Clone,
+    Scr: Scorer<Ge, Score = Sc>,
+    Ins: Inspector<Ge, Sc>,
+{
+    /// Anneal from a randomly generated genome, returning a report with the
+    /// best genome found (which may not be the final solution) and the reason
+    /// the search stopped.
+    ///
+    /// Each step mutates the current solution, and moves to the child if it's
+    /// at least as good, or with a probability that shrinks as the child gets
+    /// worse and the temperature drops if it isn't.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if mutating a genome fails.
+    pub fn search(&mut self) -> Result<SearchReport<Ge, Sc>, SimulatedAnnealingError<Mut::Error>> {
+        let (mut tracker, mut current) = start_search(
+            self.seed,
+            &self.genome_maker,
+            &self.scorer,
+            &mut self.inspector,
+        );
+        let seed = tracker.seed();
+
+        let num_steps = self.num_to_search.saturating_sub(1);
+        let mut reason = None;
+        for sample_number in 1..self.num_to_search {
+            reason = tracker.check(&self.termination);
+            if reason.is_some() {
+                break;
+            }
+            let temperature = self
+                .cooling_schedule
+                .temperature(tracker.steps(), num_steps);
+            tracker.finish_step();
+
+            let mut rng = stream_rng(seed, sample_number);
+            let child = self.mutator.mutate(current.1.clone(), &mut rng)?;
+            let score = self.scorer.score(&child);
+            tracker.record_and_inspect(sample_number, &child, &score, &mut self.inspector);
+
+            if accept(current.2.to_f64(), score.to_f64(), temperature, &mut rng) {
+                current = (sample_number, child, score);
+                self.inspector.on_samples(slice::from_ref(&current));
+            }
+        }
+
+        Ok(finish_search(
+            tracker,
+            &self.termination,
+            reason,
+            &mut self.inspector,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ec_core::{individual::scorer::FnScorer, test_results::Error};
+    use rand::distr::Uniform;
+
+    use super::*;
+    use crate::test_support::Step;
+
+    #[test]
+    fn cooling_schedules() {
+        let linear = CoolingSchedule::Linear { initial: 10.0 };
+        assert!((linear.temperature(0, 100) - 10.0).abs() < 1e-9);
+        assert!((linear.temperature(75, 100) - 2.5).abs() < 1e-9);
+        assert!(linear.temperature(200, 100).abs() < 1e-9);
+
+        let geometric = CoolingSchedule::Geometric {
+            initial: 10.0,
+            alpha: 0.5,
+        };
+        assert!((geometric.temperature(3, 100) - 1.25).abs() < 1e-9);
+
+        let logarithmic = CoolingSchedule::Logarithmic { initial: 10.0 };
+        assert!((logarithmic.temperature(0, 100) - 10.0).abs() < 1e-9);
+        assert!(logarithmic.temperature(1_000, 100) < logarithmic.temperature(10, 100));
+    }
+
+    #[test]
+    fn annealing_minimizes_errors() {
+        let report = SimulatedAnnealing::builder()
+            .num_to_search(5_000)
+            .cooling_schedule(CoolingSchedule::Geometric {
+                initial: 2.0,
+                alpha: 0.999,
+            })
+            .seed(11)
+            .genome_maker(Uniform::new(-1_000, 1_000).unwrap())
+            .mutator(Step)
+            .scorer(FnScorer(|value: &i32| Error(value.abs_diff(42))))
+            .inspector(())
+            .build()
+            .search()
+            .unwrap();
+
+        assert_eq!(report.evaluations, 5_000);
+        assert_eq!(
+            report.best.map(|best| (best.genome, best.score)),
+            Some((42, Error(0)))
+        );
+    }
+}
@@ -0,0 +1,398 @@
+use core::slice;
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, VecDeque},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
+
+use bon::Builder;
+use ec_core::{individual::scorer::Scorer, operator::mutator::Mutator};
+use rand::prelude::Distribution;
+
+use crate::{
+    hill_climber::{finish_search, sample_neighbors, start_search, Solution},
+    inspector::Inspector,
+    report::SearchReport,
+    termination::Termination,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum TabuSearchError<MutationError> {
+    #[error(transparent)]
+    Mutation(#[from] MutationError),
+
+    #[error("Tabu search needs at least one neighbor per step")]
+    NoNeighbors,
+}
+
+/// The hashes of the most recently visited genomes, which the search isn't
+/// allowed to move back to.
+///
+/// We keep hashes rather than the genomes themselves so the memory stays small
+/// for large genomes. Two different genomes with the same hash are (very
+/// rarely) treated as the same genome, which at worst makes a move tabu that
+/// shouldn't be.
+#[derive(Debug)]
+struct TabuList {
+    tenure: usize,
+    recent: VecDeque<u64>,
+    // How many times each hash appears in `recent`, since a genome can be
+    // revisited once it's no longer tabu.
+    counts: HashMap<u64, usize>,
+}
+
+impl TabuList {
+    fn new(tenure: usize) -> Self {
+        Self {
+            tenure,
+            recent: VecDeque::with_capacity(tenure),
+            counts: HashMap::new(),
+        }
+    }
+
+    fn hash(genome: &impl Hash) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        genome.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn contains(&self, genome: &impl Hash) -> bool {
+        self.counts.contains_key(&Self::hash(genome))
+    }
+
+    fn visit(&mut self, genome: &impl Hash) {
+        if self.tenure == 0 {
+            return;
+        }
+        if self.recent.len() == self.tenure {
+            if let Some(expired) = self.recent.pop_front() {
+                if let Some(count) = self.counts.get_mut(&expired) {
+                    *count -= 1;
+                    if *count == 0 {
+                        self.counts.remove(&expired);
+                    }
+                }
+            }
+        }
+        let hash = Self::hash(genome);
+        self.recent.push_back(hash);
+        *self.counts.entry(hash).or_default() += 1;
+    }
+}
+
+#[derive(Debug, Builder)]
+pub struct TabuSearch<Ge, GM, Mut, Sc, Scr, Ins>
+// You typically wouldn't put all these constraints on the struct itself, instead
+// you'd put them on the `impl` block for the struct. But I'm doing it here to
+// make the constraints more visible and (hopefully) make some of the error
+// messages more helpful for people new to Rust.
+where
+    // Genomes are remembered by their hashes.
+    Ge: Clone + Hash + Send + Sync,
+    GM: Distribution<Ge>,
+    Mut: Mutator<Ge> + Sync,
+    Mut::Error: Send,
+    Sc: Ord + PartialOrd + Send,
+    Scr: Scorer<Ge, Score = Sc> + Sync,
+    // The inspector is shown each new current solution as a
+    // (sample number, genome, score) triple.
+    Ins: Inspector<Ge, Sc>,
+{
+    // See `HillClimber` for why we need this.
+    #[builder(field)]
+    _p: PhantomData<Ge>,
+
+    #[builder(default = 1_000)]
+    num_to_search: usize,
+
+    /// The number of neighbors (mutants of the current solution) to look at in
+    /// each step.
+    #[builder(default = 10)]
+    num_neighbors: usize,
+
+    /// The number of most recently visited genomes that the search won't move
+    /// back to (unless doing so beats the best genome found so far).
+    #[builder(default = 100)]
+    tabu_tenure: usize,
+
+    /// Mutate and score the neighbors in each step in parallel. The serial and
+    /// parallel searches make the same neighbors, and make the same moves.
+    #[builder(default = false)]
+    parallel_search: bool,
+
+    /// Additional criteria for stopping the search before `num_to_search`
+    /// genomes have been evaluated.
+    #[builder(default)]
+    termination: Termination<Sc>,
+
+    /// The seed for the random number generator. If this isn't specified a
+    /// random seed is used, which is included in the returned report.
+    seed: Option<u64>,
+
+    genome_maker: GM,
+    mutator: Mut,
+    scorer: Scr,
+    inspector: Ins,
+}
+
+impl<Ge, GM, Mut, Sc, Scr, Ins> TabuSearch<Ge, GM, Mut, Sc, Scr, Ins>
+where
+    Ge: Clone + Hash + Send + Sync,
+    GM: Distribution<Ge>,
+    Mut: Mutator<Ge> + Sync,
+    Mut::Error: Send,
+    Sc: Ord + PartialOrd + Clone + Send,
+    Scr: Scorer<Ge, Score = Sc> + Sync,
+    Ins: Inspector<Ge, Sc>,
+{
+    /// Search from a randomly generated genome, returning a report with the
+    /// best genome found and the reason the search stopped.
+    ///
+    /// Each step moves to the best neighbor of the current solution that isn't
+    /// tabu, _even if it's worse_ than the current solution, which lets the
+    /// search walk out of local optima. A tabu neighbor is allowed if it's
+    /// better than every genome found so far (the "aspiration criterion"). If
+    /// every neighbor is tabu, the search stays where it is for that step.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if mutating a genome fails, or if `num_neighbors`
+    /// is zero.
+    pub fn search(&mut self) -> Result<SearchReport<Ge, Sc>, TabuSearchError<Mut::Error>> {
+        if self.num_neighbors == 0 {
+            return Err(TabuSearchError::NoNeighbors);
+        }
+        let (mut tracker, mut current) = start_search(
+            self.seed,
+            &self.genome_maker,
+            &self.scorer,
+            &mut self.inspector,
+        );
+        let seed = tracker.seed();
+        let mut tabu_list = TabuList::new(self.tabu_tenure);
+        tabu_list.visit(&current.1);
+
+        let mut reason = None;
+        for chunk_start in (1..self.num_to_search).step_by(self.num_neighbors) {
+            reason = tracker.check(&self.termination);
+            if reason.is_some() {
+                break;
+            }
+            tracker.finish_step();
+
+            let chunk_end = self.num_to_search.min(chunk_start + self.num_neighbors);
+            let neighbors = sample_neighbors(
+                &self.mutator,
+                &self.scorer,
+                &current.1,
+                seed,
+                chunk_start..chunk_end,
+                self.parallel_search,
+            )?;
+
+            // A tabu neighbor has to beat the best genome from _before_ this
+            // step to be allowed.
+            let best_score = tracker.best().map(|best| best.score.clone());
+            let mut best_allowed: Option<Solution<Ge, Sc>> = None;
+            for (sample_number, neighbor, score) in neighbors {
+                tracker.record_and_inspect(sample_number, &neighbor, &score, &mut self.inspector);
+                let aspires = best_score.as_ref().is_none_or(|best| score > *best);
+                let allowed = aspires || !tabu_list.contains(&neighbor);
+                // Like `HillClimber`, we pick the last of equally good neighbors.
+                if allowed
+                    && best_allowed
+                        .as_ref()
+                        .is_none_or(|(_, _, best_allowed_score)| score >= *best_allowed_score)
+                {
+                    best_allowed = Some((sample_number, neighbor, score));
+                }
+            }
+
+            if let Some(next) = best_allowed {
+                tabu_list.visit(&next.1);
+                current = next;
+                self.inspector.on_samples(slice::from_ref(&current));
+            }
+        }
+
+        Ok(finish_search(
+            tracker,
+            &self.termination,
+            reason,
+            &mut self.inspector,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, convert::Infallible};
+
+    use ec_core::individual::scorer::FnScorer;
+    use ec_core::test_results::{Error, Score};
+    use ec_linear::genome::bitstring::Bitstring;
+    use rand::{distr::Uniform, Rng};
+
+    use super::*;
+    use crate::inspector::on_samples;
+    use crate::test_support::{count_ones, Step};
+
+    /// A genome that's tabu whenever any genome in the same block of ten has
+    /// been visited, since the tabu list only looks at hashes.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Coarse(i32);
+
+    impl Hash for Coarse {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.0.div_euclid(10).hash(state);
+        }
+    }
+
+    impl Mutator<Coarse> for Step {
+        type Error = Infallible;
+
+        fn mutate<R: Rng + ?Sized>(
+            &self,
+            genome: Coarse,
+            rng: &mut R,
+        ) -> Result<Coarse, Self::Error> {
+            self.mutate(genome.0, rng).map(Coarse)
+        }
+    }
+
+    /// 0 is a local optimum, and going up from it leads (after one step
+    /// backwards) to the global optimum at 4.
+    fn valley_error(genome: &i32) -> Error<u32> {
+        Error(match *genome {
+            ..0 => 5 + genome.unsigned_abs(),
+            0 => 1,
+            1 => 3,
+            2 => 2,
+            3 => 1,
+            4 => 0,
+            5.. => genome.unsigned_abs() - 4,
+        })
+    }
+
+    fn search_the_valley(tabu_tenure: usize) -> SearchReport<i32, Error<u32>> {
+        TabuSearch::builder()
+            .num_to_search(201)
+            .num_neighbors(10)
+            .tabu_tenure(tabu_tenure)
+            .seed(4)
+            .genome_maker(Uniform::new_inclusive(0, 0).unwrap())
+            .mutator(Step)
+            .scorer(FnScorer(valley_error))
+            .inspector(())
+            .build()
+            .search()
+            .unwrap()
+    }
+
+    #[test]
+    fn tabu_neighbors_are_refused() {
+        // Without a tabu list the search steps up from 0 and then straight
+        // back down again.
+        let best = search_the_valley(0).best.unwrap();
+        assert_eq!((best.genome, best.score), (0, Error(1)));
+
+        // With one, going back to 0 is tabu, so it keeps going up.
+        let best = search_the_valley(10).best.unwrap();
+        assert_eq!((best.genome, best.score), (4, Error(0)));
+    }
+
+    #[test]
+    fn tabu_neighbors_that_beat_the_best_are_allowed() {
+        // Every genome from 0 to 9 is tabu as soon as we start at 0, so only
+        // the aspiration criterion lets the search go up towards 5 rather
+        // than down and away from it.
+        let report = TabuSearch::builder()
+            .num_to_search(201)
+            .num_neighbors(10)
+            .seed(4)
+            .genome_maker(Uniform::new_inclusive(0, 0).unwrap().map(Coarse))
+            .mutator(Step)
+            .scorer(FnScorer(|genome: &Coarse| Error(genome.0.abs_diff(5))))
+            .inspector(())
+            .build()
+            .search()
+            .unwrap();
+        let best = report.best.unwrap();
+        assert_eq!((best.genome, best.score), (Coarse(5), Error(0)));
+    }
+
+    /// Flips one bit at random.
+    struct FlipOne;
+
+    impl Mutator<Bitstring> for FlipOne {
+        type Error = Infallible;
+
+        fn mutate<R: Rng + ?Sized>(
+            &self,
+            mut genome: Bitstring,
+            rng: &mut R,
+        ) -> Result<Bitstring, Self::Error> {
+            let index = rng.random_range(0..genome.bits.len());
+            genome.bits[index] = !genome.bits[index];
+            Ok(genome)
+        }
+    }
+
+    /// The genomes the search moves to when maximizing the number of ones.
+    fn current_bitstrings(tabu_tenure: usize) -> Vec<Bitstring> {
+        let mut current = Vec::new();
+        TabuSearch::builder()
+            .num_to_search(401)
+            .num_neighbors(20)
+            .tabu_tenure(tabu_tenure)
+            .seed(8)
+            .genome_maker(
+                Uniform::new_inclusive(0, 0)
+                    .unwrap()
+                    .map(|_| Bitstring::from_iter([false; 8])),
+            )
+            .mutator(FlipOne)
+            .scorer(FnScorer(count_ones))
+            .inspector(on_samples(
+                |solutions: &[Solution<Bitstring, Score<usize>>]| {
+                    current.extend(solutions.iter().map(|(_, genome, _)| genome.clone()));
+                },
+            ))
+            .build()
+            .search()
+            .unwrap();
+        current
+    }
+
+    #[test]
+    fn tabu_bitstrings_arent_flipped_back() {
+        // Once it reaches all ones, every neighbor is worse, so without a
+        // tabu list the search flips a bit and then flips it straight back.
+        let current = current_bitstrings(0);
+        assert!(current.windows(3).any(|window| window[0] == window[2]));
+
+        // With one, it never returns to a bitstring it's already been at.
+        let current = current_bitstrings(100);
+        assert_eq!(current.len(), 21);
+        assert_eq!(current.iter().collect::<HashSet<_>>().len(), current.len());
+        assert!(current.contains(&Bitstring::from_iter([true; 8])));
+    }
+
+    #[test]
+    fn tabu_list_forgets_the_oldest_genomes() {
+        let mut tabu_list = TabuList::new(2);
+        tabu_list.visit(&1);
+        tabu_list.visit(&2);
+        tabu_list.visit(&1);
+        assert!(tabu_list.contains(&1));
+        assert!(tabu_list.contains(&2));
+
+        tabu_list.visit(&3);
+        assert!(!tabu_list.contains(&2));
+        // The second visit to 1 is still remembered.
+        assert!(tabu_list.contains(&1));
+
+        tabu_list.visit(&4);
+        assert!(!tabu_list.contains(&1));
+    }
+}
@@ -0,0 +1,486 @@
+use std::time::{Duration, Instant};
+
+use crate::{
+    inspector::Inspector,
+    report::{BestFound, SearchReport},
+};
+
+/// A snapshot of how far a search has gotten, used to decide whether it
+/// should stop.
+///
+/// What counts as a "step" depends on the search algorithm: it's a generation
+/// in `Run`, a (possibly multi-child) climbing step in `HillClimber`, and
+/// a sample (or a chunk of samples in parallel mode) in `RandomSearch`.
+#[derive(Debug)]
+pub struct Progress<'a, Sc> {
+    pub steps: usize,
+    pub evaluations: usize,
+    pub best_score: Option<&'a Sc>,
+    pub steps_since_improvement: usize,
+    pub elapsed: Duration,
+}
+
+/// Why a search stopped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TerminationReason {
+    /// The best score reached the target.
+    TargetScore,
+    /// The best score didn't improve for the given number of steps.
+    NoImprovement,
+    /// The search used up its evaluation budget.
+    MaxEvaluations,
+    /// The search took its maximum number of steps (e.g., generations).
+    MaxSteps,
+    /// The search ran out of (wall-clock) time.
+    TimeLimit,
+    /// Every criterion in a [`Termination::All`] was met.
+    All(Vec<TerminationReason>),
+    /// The caller finished a search that was being run one step at a time
+    /// before it would have stopped on its own.
+    Stopped,
+}
+
+/// When a search should stop, in addition to any limits built into the
+/// search itself (like `max_generations` or `num_to_search`).
+///
+/// Scores are compared the same way everywhere else in this crate, i.e.,
+/// higher scores are better. (`ec_core`'s `Error` type orders things so that
+/// lower errors are "higher".)
+///
+/// # Examples
+///
+/// Stop when we've found a solution with zero error, or after a minute,
+/// whichever comes first.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use course_helpers::termination::Termination;
+/// # use ec_core::test_results::Error;
+/// #
+/// let termination = Termination::TargetScore(Error(0)).or(Termination::TimeLimit(Duration::from_secs(60)));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub enum Termination<Sc> {
+    /// Never stop early.
+    #[default]
+    Never,
+    /// Stop when the best score is at least this good.
+    TargetScore(Sc),
+    /// Stop when the best score satisfies this predicate. This is handy when
+    /// the score type is awkward to build a target value for, e.g.,
+    /// `|results: &TestResults<Error<i64>>| results.total_result.0 == 0`.
+    BestSatisfies(fn(&Sc) -> bool),
+    /// Stop when the best score hasn't improved for this many steps.
+    NoImprovement(usize),
+    /// Stop after this many evaluations (calls to the scorer).
+    MaxEvaluations(usize),
+    /// Stop after this many steps.
+    MaxSteps(usize),
+    /// Stop once the search has been running for this long.
+    TimeLimit(Duration),
+    /// Stop as soon as any of these criteria is met.
+    Any(Vec<Self>),
+    /// Stop once all of these criteria are met. An empty `All` never stops.
+    All(Vec<Self>),
+}
+
+impl<Sc> Termination<Sc>
+where
+    Sc: PartialOrd,
+{
+    /// Stop when either `self` or `other` is met.
+    #[must_use]
+    pub fn or(self, other: Self) -> Self {
+        match self {
+            Self::Any(mut criteria) => {
+                criteria.push(other);
+                Self::Any(criteria)
+            }
+            _ => Self::Any(vec![self, other]),
+        }
+    }
+
+    /// Stop when both `self` and `other` are met.
+    #[must_use]
+    pub fn and(self, other: Self) -> Self {
+        match self {
+            Self::All(mut criteria) => {
+                criteria.push(other);
+                Self::All(criteria)
+            }
+            _ => Self::All(vec![self, other]),
+        }
+    }
+
+    /// Returns the reason the search should stop, or `None` if it should
+    /// keep going.
+    pub fn check(&self, progress: &Progress<'_, Sc>) -> Option<TerminationReason> {
+        match self {
+            Self::Never => None,
+            Self::TargetScore(target) => progress
+                .best_score
+                .filter(|best| *best >= target)
+                .map(|_| TerminationReason::TargetScore),
+            Self::BestSatisfies(predicate) => progress
+                .best_score
+                .filter(|best| predicate(best))
+                .map(|_| TerminationReason::TargetScore),
+            Self::NoImprovement(steps) => (progress.steps_since_improvement >= *steps)
+                .then_some(TerminationReason::NoImprovement),
+            Self::MaxEvaluations(evaluations) => {
+                (progress.evaluations >= *evaluations).then_some(TerminationReason::MaxEvaluations)
+            }
+            Self::MaxSteps(steps) => {
+                (progress.steps >= *steps).then_some(TerminationReason::MaxSteps)
+            }
+            Self::TimeLimit(limit) => {
+                (progress.elapsed >= *limit).then_some(TerminationReason::TimeLimit)
+            }
+            Self::Any(criteria) => criteria
+                .iter()
+                .find_map(|criterion| criterion.check(progress)),
+            Self::All(criteria) => {
+                if criteria.is_empty() {
+                    return None;
+                }
+                criteria
+                    .iter()
+                    .map(|criterion| criterion.check(progress))
+                    .collect::<Option<Vec<_>>>()
+                    .map(TerminationReason::All)
+            }
+        }
+    }
+
+    /// How many more steps a search can take before one of the step-based
+    /// criteria ([`Termination::MaxSteps`] and [`Termination::NoImprovement`])
+    /// might be met, or `None` if there aren't any. Searches that take
+    /// several steps between checks (like `RandomSearch` with
+    /// `Aggregation::Reduce`) use this so they don't step past the point
+    /// where they should have stopped.
+    pub(crate) fn steps_before_check(&self, progress: &Progress<'_, Sc>) -> Option<usize> {
+        match self {
+            Self::MaxSteps(steps) => Some(steps.saturating_sub(progress.steps)),
+            Self::NoImprovement(steps) => {
+                Some(steps.saturating_sub(progress.steps_since_improvement))
+            }
+            Self::Any(criteria) | Self::All(criteria) => criteria
+                .iter()
+                .filter_map(|criterion| criterion.steps_before_check(progress))
+                .min(),
+            Self::Never
+            | Self::TargetScore(_)
+            | Self::BestSatisfies(_)
+            | Self::MaxEvaluations(_)
+            | Self::TimeLimit(_) => None,
+        }
+    }
+
+    /// Would a best score of `score` be enough, on its own, to stop the
+    /// search? Only [`Termination::TargetScore`] and
+    /// [`Termination::BestSatisfies`] look at the score, so this is `false`
+    /// for any other criterion (or an `All` that includes one). It's cheap
+    /// enough to check after every evaluation, unlike [`Termination::check`].
+    pub fn reached_by(&self, score: &Sc) -> bool {
+        match self {
+            Self::TargetScore(target) => score >= target,
+            Self::BestSatisfies(predicate) => predicate(score),
+            Self::Any(criteria) => criteria.iter().any(|criterion| criterion.reached_by(score)),
+            Self::All(criteria) => {
+                !criteria.is_empty() && criteria.iter().all(|criterion| criterion.reached_by(score))
+            }
+            Self::Never
+            | Self::NoImprovement(_)
+            | Self::MaxEvaluations(_)
+            | Self::MaxSteps(_)
+            | Self::TimeLimit(_) => false,
+        }
+    }
+}
+
+/// Keeps track of the numbers in [`Progress`] as a search runs, along with
+/// the best genome found so far, so we can build a [`SearchReport`] at the end.
+#[derive(Debug)]
+pub(crate) struct ProgressTracker<Ge, Sc> {
+    seed: u64,
+    start: Instant,
+    steps: usize,
+    evaluations: usize,
+    best: Option<BestFound<Ge, Sc>>,
+    last_improvement_step: usize,
+}
+
+impl<Ge, Sc> ProgressTracker<Ge, Sc>
+where
+    Ge: Clone,
+    Sc: PartialOrd + Clone,
+{
+    pub(crate) fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            start: Instant::now(),
+            steps: 0,
+            evaluations: 0,
+            best: None,
+            last_improvement_step: 0,
+        }
+    }
+
+    /// A tracker for a search that's picking up where an earlier one left off,
+    /// e.g., from a checkpoint. The clock starts over, though.
+    pub(crate) fn resume(
+        seed: u64,
+        steps: usize,
+        evaluations: usize,
+        best: Option<BestFound<Ge, Sc>>,
+    ) -> Self {
+        Self {
+            seed,
+            start: Instant::now(),
+            steps,
+            evaluations,
+            last_improvement_step: best.as_ref().map_or(0, |best| best.step),
+            best,
+        }
+    }
+
+    /// Record one evaluation of `genome` with the given score, returning `true`
+    /// if it's strictly better than the best score seen so far.
+    ///
+    /// Ties go to the genome with the lower sample number, so parallel searches
+    /// end up with the same best genome regardless of the order samples are
+    /// recorded in. Replacing the best genome with an equally good one isn't an
+    /// improvement, though, so it doesn't reset the `NoImprovement` count.
+    pub(crate) fn record(&mut self, sample_number: usize, genome: &Ge, score: &Sc) -> bool {
+        self.record_at(self.steps, sample_number, genome, score)
+    }
+
+    /// [`ProgressTracker::record`] an evaluation that was made in the given
+    /// step, which might be earlier than the current step in searches (like
+    /// island runs) where several steps can be in progress at once.
+    pub(crate) fn record_at(
+        &mut self,
+        step: usize,
+        sample_number: usize,
+        genome: &Ge,
+        score: &Sc,
+    ) -> bool {
+        self.evaluations += 1;
+        let (improved, replace) = self.best.as_ref().map_or((true, true), |best| {
+            let improved = *score > best.score;
+            let earlier_tie = *score == best.score && sample_number < best.sample_number;
+            (improved, improved || earlier_tie)
+        });
+        if replace {
+            self.best = Some(BestFound {
+                genome: genome.clone(),
+                score: score.clone(),
+                sample_number,
+                step,
+            });
+        }
+        if improved {
+            self.last_improvement_step = self.last_improvement_step.max(step);
+        }
+        improved
+    }
+
+    /// [`ProgressTracker::record`] an evaluation, and tell `inspector` if
+    /// it's a new best.
+    pub(crate) fn record_and_inspect(
+        &mut self,
+        sample_number: usize,
+        genome: &Ge,
+        score: &Sc,
+        inspector: &mut impl Inspector<Ge, Sc>,
+    ) -> bool {
+        let improved = self.record(sample_number, genome, score);
+        self.inspect_if_improved(improved, inspector)
+    }
+
+    /// [`ProgressTracker::record_at`] an evaluation, and tell `inspector` if
+    /// it's a new best.
+    pub(crate) fn record_and_inspect_at(
+        &mut self,
+        step: usize,
+        sample_number: usize,
+        genome: &Ge,
+        score: &Sc,
+        inspector: &mut impl Inspector<Ge, Sc>,
+    ) -> bool {
+        let improved = self.record_at(step, sample_number, genome, score);
+        self.inspect_if_improved(improved, inspector)
+    }
+
+    fn inspect_if_improved(&self, improved: bool, inspector: &mut impl Inspector<Ge, Sc>) -> bool {
+        if let Some(best) = self.best.as_ref().filter(|_| improved) {
+            inspector.on_new_best(best);
+        }
+        improved
+    }
+
+    /// Record `num_evaluations` evaluations that were summarized elsewhere
+    /// (e.g., by parallel workers), where `best` was the best of them and was
+    /// made in step `best_step`, and tell `inspector` if it's a new best.
+    pub(crate) fn record_summarized(
+        &mut self,
+        num_evaluations: usize,
+        best: Option<&(usize, Ge, Sc)>,
+        best_step: usize,
+        inspector: &mut impl Inspector<Ge, Sc>,
+    ) -> bool {
+        let Some((sample_number, genome, score)) = best else {
+            self.evaluations += num_evaluations;
+            return false;
+        };
+        let improved =
+            self.record_and_inspect_at(best_step, *sample_number, genome, score, inspector);
+        self.evaluations += num_evaluations.saturating_sub(1);
+        improved
+    }
+
+    pub(crate) fn finish_step(&mut self) {
+        self.steps += 1;
+    }
+
+    /// Move the step count forward to `step`, if it isn't there already. This
+    /// is for searches (like island runs) where several steps can be in
+    /// progress at once.
+    pub(crate) fn advance_to(&mut self, step: usize) {
+        self.steps = self.steps.max(step);
+    }
+
+    pub(crate) const fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub(crate) const fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub(crate) const fn evaluations(&self) -> usize {
+        self.evaluations
+    }
+
+    pub(crate) const fn best(&self) -> Option<&BestFound<Ge, Sc>> {
+        self.best.as_ref()
+    }
+
+    pub(crate) fn progress(&self) -> Progress<'_, Sc> {
+        Progress {
+            steps: self.steps,
+            evaluations: self.evaluations,
+            best_score: self.best.as_ref().map(|best| &best.score),
+            steps_since_improvement: self.steps - self.last_improvement_step,
+            elapsed: self.start.elapsed(),
+        }
+    }
+
+    pub(crate) fn check(&self, termination: &Termination<Sc>) -> Option<TerminationReason> {
+        termination.check(&self.progress())
+    }
+
+    pub(crate) fn into_report(self, termination_reason: TerminationReason) -> SearchReport<Ge, Sc> {
+        SearchReport {
+            best: self.best,
+            evaluations: self.evaluations,
+            steps: self.steps,
+            elapsed: self.start.elapsed(),
+            termination_reason,
+            seed: self.seed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress(steps: usize, best_score: Option<&i32>) -> Progress<'_, i32> {
+        Progress {
+            steps,
+            evaluations: steps * 10,
+            best_score,
+            steps_since_improvement: steps,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn target_score() {
+        let termination = Termination::TargetScore(5);
+        assert_eq!(termination.check(&progress(1, None)), None);
+        assert_eq!(termination.check(&progress(1, Some(&4))), None);
+        assert_eq!(
+            termination.check(&progress(1, Some(&5))),
+            Some(TerminationReason::TargetScore)
+        );
+
+        assert!(!termination.reached_by(&4));
+        assert!(termination.reached_by(&5));
+        assert!(termination
+            .clone()
+            .or(Termination::MaxSteps(10))
+            .reached_by(&5));
+        // The score alone can't tell us if we've taken enough steps.
+        assert!(!termination.and(Termination::MaxSteps(10)).reached_by(&5));
+    }
+
+    #[test]
+    fn any_and_all() {
+        let any = Termination::MaxSteps(10).or(Termination::MaxEvaluations(50));
+        assert_eq!(any.check(&progress(4, None)), None);
+        assert_eq!(
+            any.check(&progress(5, None)),
+            Some(TerminationReason::MaxEvaluations)
+        );
+
+        let all = Termination::MaxSteps(10).and(Termination::NoImprovement(3));
+        assert_eq!(all.check(&progress(5, None)), None);
+        assert_eq!(
+            all.check(&progress(10, None)),
+            Some(TerminationReason::All(vec![
+                TerminationReason::MaxSteps,
+                TerminationReason::NoImprovement
+            ]))
+        );
+        assert_eq!(
+            Termination::<i32>::All(vec![]).check(&progress(10, None)),
+            None
+        );
+    }
+
+    #[test]
+    fn tracker_counts_improvements() {
+        let mut tracker = ProgressTracker::new(0);
+        assert!(tracker.record(0, &"a", &3));
+        tracker.finish_step();
+        assert!(!tracker.record(1, &"b", &2));
+        assert!(!tracker.record(2, &"c", &3));
+        tracker.finish_step();
+        let progress = tracker.progress();
+        assert_eq!(progress.evaluations, 3);
+        assert_eq!(progress.best_score, Some(&3));
+        assert_eq!(progress.steps_since_improvement, 2);
+
+        let report = tracker.into_report(TerminationReason::MaxSteps);
+        let best = report.best.unwrap();
+        assert_eq!((best.sample_number, best.genome, best.score), (0, "a", 3));
+    }
+
+    #[test]
+    fn ties_are_not_improvements() {
+        let mut tracker = ProgressTracker::new(0);
+        tracker.finish_step();
+        assert!(tracker.record(5, &"a", &3));
+        tracker.finish_step();
+        tracker.finish_step();
+        // An equally good genome with an earlier sample number (e.g., from a
+        // parallel chunk that finished late) becomes the best, but it isn't
+        // an improvement.
+        assert!(!tracker.record(2, &"b", &3));
+        assert_eq!(tracker.progress().steps_since_improvement, 2);
+        let best = tracker.best().unwrap();
+        assert_eq!((best.sample_number, best.genome, best.step), (2, "b", 3));
+    }
+}
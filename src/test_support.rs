@@ -0,0 +1,20 @@
+use std::convert::Infallible;
+
+use ec_core::{operator::mutator::Mutator, test_results::Score};
+use ec_linear::genome::bitstring::Bitstring;
+use rand::Rng;
+
+/// Adds or subtracts one from an `i32` genome.
+pub(crate) struct Step;
+
+impl Mutator<i32> for Step {
+    type Error = Infallible;
+
+    fn mutate<R: Rng + ?Sized>(&self, genome: i32, rng: &mut R) -> Result<i32, Self::Error> {
+        Ok(if rng.random() { genome + 1 } else { genome - 1 })
+    }
+}
+
+pub(crate) fn count_ones(bitstring: &Bitstring) -> Score<usize> {
+    Score(bitstring.bits.iter().filter(|&&bit| bit).count())
+}